@@ -1,27 +1,34 @@
 use clap::{Args, CommandFactory, Parser, Subcommand};
 use clap_complete::Shell;
 
-use crate::domain::platform::{TargetArch, TargetOs};
+use crate::domain::platform::{TargetArch, TargetLibc, TargetOs};
 use crate::error::AppError;
 use crate::http::query::{InstallMethod, InstallQueryOptions};
 use crate::http::responses::ScriptResponse;
-use crate::services::installer;
-use crate::supported_apps::{self, Repo, SupportedApp};
+use crate::providers::PROVIDERS;
+use crate::services::installer::{self, LoadedApp};
+use crate::services::registry_check::{self, TargetCheck};
+use crate::supported_apps::{self, AppSource, Repo, SupportedApp};
 use crossterm::{
   execute,
   style::{style, Color, Print, Stylize},
 };
 use flate2::read::GzDecoder;
+use globset::Glob;
+use lzma_rust2::XzReader;
 use mime::Mime;
 use serde_json::Value;
 use std::env;
 use std::fs::{self, File};
-use std::io;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tar::Archive;
 use zip::ZipArchive;
 
+/// Levels of an archive shown when the binary's location is unknown.
+const ARCHIVE_TREE_DEPTH: usize = 2;
+
 #[derive(Parser)]
 #[command(name = "termlibs")]
 #[command(about = "Termlibs server and install script CLI")]
@@ -47,6 +54,9 @@ pub(crate) enum Commands {
   Script(ScriptCommands),
   /// Native installer (placeholder for Rust implementation)
   Install(InstallArgs),
+  /// App registry maintenance
+  #[command(subcommand)]
+  Registry(RegistryCommands),
   /// Generate shell completion scripts
   Completions(CompletionsArgs),
 }
@@ -96,13 +106,16 @@ pub(crate) struct InstallArgs {
   /// Target architecture
   #[arg(long)]
   arch: Option<String>,
+  /// C library of Linux builds: gnu, musl or none (default: this host's)
+  #[arg(long)]
+  libc: Option<String>,
   /// Release version or tag (default: latest)
   #[arg(long)]
   version: Option<String>,
   /// Installation prefix (default: $HOME/.local)
   #[arg(long)]
   prefix: Option<String>,
-  /// Install method hint: binary or installer
+  /// Install method hint: binary, installer, or go/cargo to install from source
   #[arg(long)]
   method: Option<String>,
   /// Download-only mode
@@ -123,7 +136,8 @@ impl InstallArgs {
   pub(crate) async fn run(&self) -> Result<CliInstallOutput, AppError> {
     let plan = NativeInstallPlan::from_args(self)?;
     let tempdir = create_tempdir()?;
-    let (links, labels) = plan.resolve_links().await?;
+    let (supported_app, loaded, labels) = plan.resolve_links().await?;
+    let links = &loaded.links;
     let selection = if labels.len() == 1 {
       ct_write_line(
         style(format!("Only one download found; selecting {}", labels[0])).with(Color::Yellow),
@@ -155,34 +169,69 @@ impl InstallArgs {
     } else if is_archive(&selected_link.content_type, &selected_link.name) {
       let downloaded_path =
         download_asset(&selected_link.url, &tempdir, &selected_link.name).await?;
-      let entries = list_archive_entries(&downloaded_path)?;
+      let binaries = supported_app.archive_binaries(&loaded.version, &loaded.target);
+      // list one level past the known binaries' directories, or a browsable tree otherwise
+      let depth = match (
+        binaries.is_empty(),
+        supported_app.archive_depth_for(&loaded.target.os),
+      ) {
+        (false, Some(depth)) => depth + 1,
+        _ => ARCHIVE_TREE_DEPTH,
+      };
+      let entries = list_archive_entries(&downloaded_path, depth)?;
       if entries.is_empty() {
         return Err(AppError::InvalidInput("archive is empty".to_string()));
       }
-      if entries.len() == 1 {
-        ct_write_line(
-          style(format!("Only one entry found; selecting {}", entries[0])).with(Color::Yellow),
-        )?;
-        chosen_archive_entry = Some(entries[0].clone());
+      let default_dir = env::current_dir()
+        .map(|p| p.join("bin"))
+        .unwrap_or_else(|_| PathBuf::from("bin"));
+
+      if !binaries.is_empty() {
+        let mut installed = vec![];
+        for binary in &binaries {
+          let entry = find_archive_entry(&entries, &binary.archive_path)?.ok_or_else(|| {
+            AppError::InvalidInput(format!(
+              "{} not found in {}",
+              binary.archive_path, selected_link.name
+            ))
+          })?;
+          ct_write_line(style(format!("Extracting {}", entry)).with(Color::Yellow))?;
+          let extracted = extract_archive_entry(&downloaded_path, entry, &tempdir)?;
+          let ext = Path::new(entry)
+            .extension()
+            .map(|e| format!(".{}", e.to_string_lossy()))
+            .unwrap_or_default();
+          let final_name = format!("{}{}", binary.install_name, ext);
+          let final_path = finalize_install(&extracted, &default_dir, &final_name)?;
+          ct_write_line(style(format!("Copied to {}", final_path.display())).with(Color::Green))?;
+          installed.push((entry.clone(), final_name));
+        }
+        let (entries, names): (Vec<String>, Vec<String>) = installed.into_iter().unzip();
+        chosen_path = Some(default_dir);
+        chosen_name = Some(names.join(", "));
+        chosen_archive_entry = Some(entries.join(", "));
       } else {
-        render_tree(&entries)?;
-        let archive_choice = prompt_for_choice(&entries)?;
-        chosen_archive_entry = Some(entries[archive_choice].clone());
-      }
+        let entry = if entries.len() == 1 {
+          ct_write_line(
+            style(format!("Only one entry found; selecting {}", entries[0])).with(Color::Yellow),
+          )?;
+          entries[0].clone()
+        } else {
+          render_tree(&entries)?;
+          let archive_choice = prompt_for_choice(&entries)?;
+          entries[archive_choice].clone()
+        };
 
-      if let Some(entry) = &chosen_archive_entry {
-        let extracted = extract_archive_entry(&downloaded_path, entry, &tempdir)?;
-        let default_dir = env::current_dir()
-          .map(|p| p.join("bin"))
-          .unwrap_or_else(|_| PathBuf::from("bin"));
-        let default_name = Path::new(entry)
+        let extracted = extract_archive_entry(&downloaded_path, &entry, &tempdir)?;
+        let default_name = Path::new(&entry)
           .file_name()
           .map(|f| f.to_string_lossy().to_string())
           .unwrap_or_else(|| entry.to_string());
-        let (dest_dir, final_name) = prompt_destination(entry, &default_dir, &default_name)?;
+        let (dest_dir, final_name) = prompt_destination(&entry, &default_dir, &default_name)?;
         let final_path = finalize_install(&extracted, &dest_dir, &final_name)?;
         chosen_path = Some(dest_dir);
         chosen_name = Some(final_name);
+        chosen_archive_entry = Some(entry);
         ct_write_line(style(format!("Copied to {}", final_path.display())).with(Color::Green))?;
       }
     }
@@ -219,6 +268,9 @@ impl NativeInstallPlan {
     let env_arch = env::var("TERMLIBS_ARCH")
       .ok()
       .map(|v| TargetArch::from(v.as_str()));
+    let env_libc = env::var("TERMLIBS_LIBC")
+      .ok()
+      .map(|v| TargetLibc::from(v.as_str()));
     let env_version = env::var("TERMLIBS_VERSION").ok();
     let env_prefix = env::var("TERMLIBS_PREFIX").ok();
     let env_method = env::var("TERMLIBS_METHOD")
@@ -241,6 +293,12 @@ impl NativeInstallPlan {
       .map(|v| TargetArch::from(v.as_str()))
       .or(env_arch)
       .unwrap_or_else(host_arch);
+    let libc = args
+      .libc
+      .as_ref()
+      .map(|v| TargetLibc::from(v.as_str()))
+      .or(env_libc)
+      .or_else(|| host_libc(&os));
     let version = args.version.clone().or(env_version);
     let prefix = args.prefix.clone().or(env_prefix);
     let method = args
@@ -266,7 +324,7 @@ impl NativeInstallPlan {
       }
     };
 
-    let query = InstallQueryOptions::new(
+    let mut query = InstallQueryOptions::new(
       None,
       version,
       prefix,
@@ -279,37 +337,37 @@ impl NativeInstallPlan {
       log_level,
       Some(false),
     );
+    query.libc = libc;
 
     Ok(Self { target, query })
   }
 
-  async fn resolve_links(
-    &self,
-  ) -> Result<(Vec<crate::supported_apps::DownloadInfo>, Vec<String>), AppError> {
+  async fn resolve_links(&self) -> Result<(SupportedApp, LoadedApp, Vec<String>), AppError> {
     let supported_app = match &self.target {
       InstallTarget::SupportedApp(app) => {
         supported_apps::get_app(app).ok_or_else(|| AppError::UnsupportedApp(app.to_string()))?
       }
       InstallTarget::Github { owner, repo } => {
         let name = format!("{}/{}", owner, repo);
-        SupportedApp::new(&name, Repo::github(&name), "github")
+        SupportedApp::new(&name, Repo::github(&name), AppSource::Github)
       }
     };
 
-    let (_, links) = installer::load_app(&self.query, &supported_app).await?;
-    if links.is_empty() {
+    let loaded = installer::load_app(&self.query, &supported_app).await?;
+    if loaded.links.is_empty() {
       return Err(AppError::NoMatchingAssets {
         repo: supported_app.shortname,
         target: self.query.os.to_string(),
       });
     }
 
-    let labels = links
+    let labels = loaded
+      .links
       .iter()
       .map(|link| format!("{} ({})", link.name, link.content_type))
       .collect();
 
-    Ok((links, labels))
+    Ok((supported_app, loaded, labels))
   }
 }
 
@@ -440,6 +498,27 @@ fn default_binary_name(
   }
 }
 
+/// The entry `archive_path` names, falling back to its `.exe` as Windows archives ship it.
+fn find_archive_entry<'a>(
+  entries: &'a [String],
+  archive_path: &str,
+) -> Result<Option<&'a String>, AppError> {
+  for pattern in [archive_path.to_string(), format!("{}.exe", archive_path)] {
+    let matcher = Glob::new(&pattern)
+      .map_err(|err| {
+        AppError::InvalidInput(format!("invalid archive_path '{}': {}", archive_path, err))
+      })?
+      .compile_matcher();
+    if let Some(entry) = entries
+      .iter()
+      .find(|entry| matcher.is_match(entry.as_str()))
+    {
+      return Ok(Some(entry));
+    }
+  }
+  Ok(None)
+}
+
 fn is_archive(content_type: &Mime, name: &str) -> bool {
   let sub = content_type.subtype().as_str();
   let full = content_type.to_string();
@@ -481,7 +560,7 @@ async fn download_asset(
   Ok(path)
 }
 
-fn list_archive_entries(path: &Path) -> Result<Vec<String>, AppError> {
+fn list_archive_entries(path: &Path, depth: usize) -> Result<Vec<String>, AppError> {
   let lower = path
     .extension()
     .map(|e| e.to_string_lossy().to_ascii_lowercase())
@@ -501,39 +580,10 @@ fn list_archive_entries(path: &Path) -> Result<Vec<String>, AppError> {
       let name = file.name().to_string();
       entries.push(name);
     }
-    return Ok(limit_depth(entries, 2));
-  }
-
-  if lower == "gz"
-    || path.to_string_lossy().ends_with(".tar.gz")
-    || path.to_string_lossy().ends_with(".tgz")
-  {
-    let file = File::open(path).map_err(|err| {
-      AppError::InvalidInput(format!("failed to open {}: {}", path.display(), err))
-    })?;
-    let decoder = GzDecoder::new(file);
-    let mut archive = Archive::new(decoder);
-    let mut entries = Vec::new();
-    for entry in archive
-      .entries()
-      .map_err(|err| AppError::InvalidInput(format!("failed to read tar: {}", err)))?
-    {
-      let entry = entry
-        .map_err(|err| AppError::InvalidInput(format!("failed to read tar entry: {}", err)))?;
-      let path = entry
-        .path()
-        .map_err(|err| AppError::InvalidInput(format!("failed to read tar path: {}", err)))?;
-      let path_str = path.to_string_lossy().to_string();
-      entries.push(path_str);
-    }
-    return Ok(limit_depth(entries, 2));
+    return Ok(limit_depth(entries, depth));
   }
 
-  if lower == "tar" {
-    let file = File::open(path).map_err(|err| {
-      AppError::InvalidInput(format!("failed to open {}: {}", path.display(), err))
-    })?;
-    let mut archive = Archive::new(file);
+  if let Some(mut archive) = open_tar(path)? {
     let mut entries = Vec::new();
     for entry in archive
       .entries()
@@ -547,7 +597,7 @@ fn list_archive_entries(path: &Path) -> Result<Vec<String>, AppError> {
       let path_str = path.to_string_lossy().to_string();
       entries.push(path_str);
     }
-    return Ok(limit_depth(entries, 2));
+    return Ok(limit_depth(entries, depth));
   }
 
   Err(AppError::InvalidInput(
@@ -555,6 +605,26 @@ fn list_archive_entries(path: &Path) -> Result<Vec<String>, AppError> {
   ))
 }
 
+/// Opens a tarball, decompressing gzip and xz by file name. `None` for anything else.
+fn open_tar(path: &Path) -> Result<Option<Archive<Box<dyn Read>>>, AppError> {
+  let name = path.to_string_lossy().to_ascii_lowercase();
+  let is_gzip = name.ends_with(".gz") || name.ends_with(".tgz");
+  let is_xz = name.ends_with(".xz") || name.ends_with(".txz");
+  if !(is_gzip || is_xz || name.ends_with(".tar")) {
+    return Ok(None);
+  }
+  let file = File::open(path)
+    .map_err(|err| AppError::InvalidInput(format!("failed to open {}: {}", path.display(), err)))?;
+  let reader: Box<dyn Read> = if is_gzip {
+    Box::new(GzDecoder::new(file))
+  } else if is_xz {
+    Box::new(XzReader::new(file, true))
+  } else {
+    Box::new(file)
+  };
+  Ok(Some(Archive::new(reader)))
+}
+
 fn limit_depth(entries: Vec<String>, depth: usize) -> Vec<String> {
   entries
     .into_iter()
@@ -570,10 +640,12 @@ fn limit_depth(entries: Vec<String>, depth: usize) -> Vec<String> {
 }
 
 fn render_tree(entries: &[String]) -> Result<(), AppError> {
-  ct_write_line(style("Archive contents (depth 2):").with(Color::Yellow))?;
+  ct_write_line(
+    style(format!("Archive contents (depth {}):", ARCHIVE_TREE_DEPTH)).with(Color::Yellow),
+  )?;
   for entry in entries {
     let indent = entry.matches('/').count();
-    let prefix = "  ".repeat(indent.min(2));
+    let prefix = "  ".repeat(indent.min(ARCHIVE_TREE_DEPTH));
     ct_write_line(style(format!("{}- {}", prefix, entry)).with(Color::Cyan))?;
   }
   Ok(())
@@ -622,74 +694,31 @@ fn extract_archive_entry(
     return Ok(out_path);
   }
 
-  let is_gzip = lower == "gz"
-    || archive_path.to_string_lossy().ends_with(".tar.gz")
-    || archive_path.to_string_lossy().ends_with(".tgz");
-  let is_tar = lower == "tar" || is_gzip;
-  if is_tar {
-    let file = File::open(archive_path).map_err(|err| {
-      AppError::InvalidInput(format!(
-        "failed to open {}: {}",
-        archive_path.display(),
-        err
-      ))
-    })?;
-    if is_gzip {
-      let decoder = GzDecoder::new(file);
-      let mut archive = Archive::new(decoder);
-      for entry in archive
-        .entries()
-        .map_err(|err| AppError::InvalidInput(format!("failed to read tar: {}", err)))?
-      {
-        let mut entry = entry
-          .map_err(|err| AppError::InvalidInput(format!("failed to read tar entry: {}", err)))?;
-        let path = entry
-          .path()
-          .map_err(|err| AppError::InvalidInput(format!("failed to read tar path: {}", err)))?;
-        let path_str = path.to_string_lossy().to_string();
-        if path_str == entry_name {
-          let file_name = Path::new(entry_name)
-            .file_name()
-            .map(|f| f.to_string_lossy().to_string())
-            .unwrap_or_else(|| entry_name.to_string());
-          let mut out_path = tempdir.to_path_buf();
-          out_path.push(file_name);
-          let mut out = File::create(&out_path).map_err(|err| {
-            AppError::InvalidInput(format!("failed to create {}: {}", out_path.display(), err))
-          })?;
-          io::copy(&mut entry, &mut out).map_err(|err| {
-            AppError::InvalidInput(format!("failed to extract {}: {}", entry_name, err))
-          })?;
-          return Ok(out_path);
-        }
-      }
-    } else {
-      let mut archive = Archive::new(file);
-      for entry in archive
-        .entries()
-        .map_err(|err| AppError::InvalidInput(format!("failed to read tar: {}", err)))?
-      {
-        let mut entry = entry
-          .map_err(|err| AppError::InvalidInput(format!("failed to read tar entry: {}", err)))?;
-        let path = entry
-          .path()
-          .map_err(|err| AppError::InvalidInput(format!("failed to read tar path: {}", err)))?;
-        let path_str = path.to_string_lossy().to_string();
-        if path_str == entry_name {
-          let file_name = Path::new(entry_name)
-            .file_name()
-            .map(|f| f.to_string_lossy().to_string())
-            .unwrap_or_else(|| entry_name.to_string());
-          let mut out_path = tempdir.to_path_buf();
-          out_path.push(file_name);
-          let mut out = File::create(&out_path).map_err(|err| {
-            AppError::InvalidInput(format!("failed to create {}: {}", out_path.display(), err))
-          })?;
-          io::copy(&mut entry, &mut out).map_err(|err| {
-            AppError::InvalidInput(format!("failed to extract {}: {}", entry_name, err))
-          })?;
-          return Ok(out_path);
-        }
+  if let Some(mut archive) = open_tar(archive_path)? {
+    for entry in archive
+      .entries()
+      .map_err(|err| AppError::InvalidInput(format!("failed to read tar: {}", err)))?
+    {
+      let mut entry = entry
+        .map_err(|err| AppError::InvalidInput(format!("failed to read tar entry: {}", err)))?;
+      let path = entry
+        .path()
+        .map_err(|err| AppError::InvalidInput(format!("failed to read tar path: {}", err)))?;
+      let path_str = path.to_string_lossy().to_string();
+      if path_str == entry_name {
+        let file_name = Path::new(entry_name)
+          .file_name()
+          .map(|f| f.to_string_lossy().to_string())
+          .unwrap_or_else(|| entry_name.to_string());
+        let mut out_path = tempdir.to_path_buf();
+        out_path.push(file_name);
+        let mut out = File::create(&out_path).map_err(|err| {
+          AppError::InvalidInput(format!("failed to create {}: {}", out_path.display(), err))
+        })?;
+        io::copy(&mut entry, &mut out).map_err(|err| {
+          AppError::InvalidInput(format!("failed to extract {}: {}", entry_name, err))
+        })?;
+        return Ok(out_path);
       }
     }
     return Err(AppError::InvalidInput(format!(
@@ -742,13 +771,16 @@ pub(crate) struct ScriptInstallArgs {
   /// Target architecture
   #[arg(long)]
   arch: Option<String>,
+  /// C library of Linux builds: gnu, musl or none (default: detected by the script)
+  #[arg(long)]
+  libc: Option<String>,
   /// Release version or tag (default: latest)
   #[arg(long)]
   version: Option<String>,
   /// Installation prefix (default: $HOME/.local)
   #[arg(long)]
   prefix: Option<String>,
-  /// Install method hint: binary or installer
+  /// Install method hint: binary, installer, or go/cargo to install from source
   #[arg(long)]
   method: Option<String>,
   /// Download-only mode
@@ -763,9 +795,12 @@ pub(crate) struct ScriptInstallArgs {
   /// Log level injected into the script
   #[arg(long)]
   log_level: Option<String>,
-  /// Output JSON map of filename to download URL (no script rendering)
+  /// Output JSON map of filename to download URL, SHA-256 digest and score (no script rendering)
   #[arg(long)]
   links_only: bool,
+  /// Use the best ranked asset instead of asking when several match
+  #[arg(long)]
+  auto_select: bool,
 }
 
 impl ScriptInstallArgs {
@@ -797,20 +832,23 @@ impl ScriptInstallArgs {
       self.log_level.clone(),
       Some(false),
     );
+    query.auto_select = self.auto_select;
+    if let Some(libc) = &self.libc {
+      query.libc = Some(TargetLibc::from(libc.as_str()));
+    }
 
     if self.links_only {
       let links = match self.target.as_slice() {
         [app] => {
           let supported_app = supported_apps::get_app(app)
             .ok_or_else(|| AppError::UnsupportedApp(app.to_string()))?;
-          let (_, links) = installer::load_app(&query, &supported_app).await?;
-          links
+          installer::load_app(&query, &supported_app).await?.links
         }
         [user, repo] => {
           let app_name = format!("{}/{}", user, repo);
-          let supported_app = SupportedApp::new(&app_name, Repo::github(&app_name), "github");
-          let (_, links) = installer::load_app(&query, &supported_app).await?;
-          links
+          let supported_app =
+            SupportedApp::new(&app_name, Repo::github(&app_name), AppSource::Github);
+          installer::load_app(&query, &supported_app).await?.links
         }
         _ => {
           return Err(AppError::InvalidInput(
@@ -841,6 +879,118 @@ pub(crate) enum CliInstallOutput {
   Links(String),
 }
 
+#[derive(Subcommand)]
+pub(crate) enum RegistryCommands {
+  /// Resolve every app for each of its platforms and report assets that no longer match
+  Check(RegistryCheckArgs),
+}
+
+#[derive(Args, Debug)]
+pub(crate) struct RegistryCheckArgs {
+  /// Apps to check (default: the whole registry)
+  #[arg(value_name = "APP")]
+  apps: Vec<String>,
+  /// Release version or tag to check (default: latest)
+  #[arg(long)]
+  version: Option<String>,
+  /// Output JSON instead of a table
+  #[arg(long)]
+  json: bool,
+  /// Do not download archives to verify archive_path
+  #[arg(long)]
+  skip_archives: bool,
+}
+
+pub(crate) struct RegistryCheckOutput {
+  pub(crate) body: String,
+  pub(crate) regressions: usize,
+}
+
+impl RegistryCheckArgs {
+  pub(crate) async fn run(&self) -> Result<RegistryCheckOutput, AppError> {
+    let apps = if self.apps.is_empty() {
+      supported_apps::all_apps()
+    } else {
+      self
+        .apps
+        .iter()
+        .map(|app| {
+          supported_apps::get_app(app).ok_or_else(|| AppError::UnsupportedApp(app.to_string()))
+        })
+        .collect::<Result<_, _>>()?
+    };
+    let tempdir = create_tempdir()?;
+
+    let mut checks = vec![];
+    for app in &apps {
+      // python packages are not built per platform, one lookup covers them all
+      let platforms = match PROVIDERS.get(app.source)?.platform_specific() {
+        true => &app.platforms[..],
+        false => &app.platforms[..app.platforms.len().min(1)],
+      };
+      for target in platforms {
+        let query = InstallQueryOptions::new(
+          Some(app.shortname.clone()),
+          self.version.clone(),
+          None,
+          Some(target.arch.clone()),
+          Some(target.os.clone()),
+          None,
+          None,
+          None,
+          Some(true),
+          None,
+          Some(false),
+        );
+        let loaded = installer::load_app(&query, app).await;
+        let mut check = TargetCheck::new(app, target, &loaded);
+        if let (Ok(loaded), false) = (&loaded, self.skip_archives) {
+          self.check_archive(app, loaded, &tempdir, &mut check).await;
+        }
+        checks.push(check);
+      }
+    }
+    let _ = fs::remove_dir_all(&tempdir);
+
+    let regressions = checks.iter().filter(|check| check.regression).count();
+    let body = if self.json {
+      serde_json::to_string_pretty(&checks)
+        .map_err(|err| AppError::InvalidInput(format!("failed to render report: {}", err)))?
+        + "\n"
+    } else {
+      registry_check::render_table(&checks)
+    };
+    Ok(RegistryCheckOutput { body, regressions })
+  }
+
+  async fn check_archive(
+    &self,
+    app: &SupportedApp,
+    loaded: &LoadedApp,
+    tempdir: &Path,
+    check: &mut TargetCheck,
+  ) {
+    let binaries = app.archive_binaries(&loaded.version, &loaded.target);
+    let ([link], false, Some(depth)) = (
+      loaded.links.as_slice(),
+      binaries.is_empty(),
+      app.archive_depth_for(&loaded.target.os),
+    ) else {
+      return;
+    };
+    let entries = match download_asset(&link.url, tempdir, &link.name).await {
+      Ok(path) => list_archive_entries(&path, depth + 1),
+      Err(err) => Err(err),
+    };
+    match entries {
+      Ok(entries) => check.check_archive(&binaries, &entries),
+      Err(err) => check
+        .notes
+        .push(format!("archive not inspected: {}", err.message())),
+    }
+  }
+}
+
 fn host_os() -> TargetOs {
   let os = env::consts::OS;
   TargetOs::identify(os)
@@ -851,10 +1001,36 @@ fn host_arch() -> TargetArch {
   TargetArch::identify(arch)
 }
 
+/// The C library this host runs, by the musl loader's presence, when `os` is the host's Linux.
+fn host_libc(os: &TargetOs) -> Option<TargetLibc> {
+  if os != &host_os() || os != &TargetOs::Linux {
+    return None;
+  }
+  let musl_loader = fs::read_dir("/lib")
+    .map(|entries| {
+      entries
+        .flatten()
+        .any(|entry| entry.file_name().to_string_lossy().starts_with("ld-musl-"))
+    })
+    .unwrap_or(false);
+  match musl_loader {
+    true => Some(TargetLibc::Musl),
+    false => Some(TargetLibc::Gnu),
+  }
+}
+
 fn render_links_for_cli(links: &[crate::supported_apps::DownloadInfo]) -> String {
   let mut map = serde_json::Map::with_capacity(links.len());
   for link in links {
-    map.insert(link.name.clone(), Value::String(link.url.to_string()));
+    map.insert(
+      link.name.clone(),
+      serde_json::json!({
+        "url": link.url.as_str(),
+        "sha256": link.sha256,
+        "score": link.score,
+        "fallback": link.fallback.as_ref().map(ToString::to_string),
+      }),
+    );
   }
   serde_json::to_string(&Value::Object(map)).unwrap_or_else(|_| "{}".to_string())
 }
@@ -866,3 +1042,48 @@ pub(crate) fn parse() -> Cli {
 pub(crate) fn build_command() -> clap::Command {
   Cli::command()
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lzma_rust2::{XzOptions, XzWriter};
+
+  #[test]
+  fn windows_archives_fall_back_to_exe_entries() {
+    let entries = ["README.md".to_string(), "uv.exe".to_string()];
+    assert_eq!(
+      find_archive_entry(&entries, "uv").unwrap(),
+      Some(&entries[1])
+    );
+    assert_eq!(find_archive_entry(&entries, "uvx").unwrap(), None);
+  }
+
+  #[test]
+  fn xz_tarballs_are_listed_and_extracted() {
+    let dir = env::temp_dir().join(format!("termlibs-xz-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let archive_path = dir.join("shellcheck-v0.10.0.linux.x86_64.tar.xz");
+    let mut builder = tar::Builder::new(Vec::new());
+    let body = b"#!/bin/sh\n";
+    let mut header = tar::Header::new_gnu();
+    header.set_size(body.len() as u64);
+    header.set_mode(0o755);
+    header.set_cksum();
+    builder
+      .append_data(&mut header, "shellcheck-v0.10.0/shellcheck", &body[..])
+      .unwrap();
+    let mut writer = XzWriter::new(
+      File::create(&archive_path).unwrap(),
+      XzOptions::with_preset(1),
+    )
+    .unwrap();
+    io::Write::write_all(&mut writer, &builder.into_inner().unwrap()).unwrap();
+    writer.finish().unwrap();
+
+    let entries = list_archive_entries(&archive_path, 2).unwrap();
+    assert_eq!(entries, ["shellcheck-v0.10.0/shellcheck"]);
+    let extracted = extract_archive_entry(&archive_path, &entries[0], &dir).unwrap();
+    assert_eq!(fs::read(&extracted).unwrap(), body);
+    fs::remove_dir_all(&dir).unwrap();
+  }
+}
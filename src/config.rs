@@ -1,14 +1,36 @@
 use anyhow::{Context, Result};
+use arc_swap::ArcSwap;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct Config {
   pub(crate) server: ServerConfig,
   pub(crate) cache: CacheConfig,
   pub(crate) github: GithubConfig,
+  #[serde(default)]
+  pub(crate) registry: RegistryConfig,
+  #[serde(default)]
+  pub(crate) python: PythonConfig,
+  #[serde(default)]
+  pub(crate) gitlab: GitlabConfig,
+  #[serde(default)]
+  pub(crate) forgejo: ForgejoConfig,
+  #[serde(default)]
+  pub(crate) hashicorp: HashicorpConfig,
+  #[serde(default)]
+  pub(crate) cargo: CargoConfig,
+  #[serde(default)]
+  pub(crate) npm: NpmConfig,
+  #[serde(default)]
+  pub(crate) oci: OciConfig,
+  #[serde(default)]
+  pub(crate) checksums: ChecksumConfig,
+  #[serde(default)]
+  pub(crate) scoring: ScoringConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +53,413 @@ pub(crate) struct GithubReleaseCacheConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct GithubConfig {
   pub(crate) api_timeout_seconds: u64,
+  /// Personal access token; `GITHUB_TOKEN` or `GH_TOKEN` is used when unset
+  #[serde(default)]
+  pub(crate) token: Option<String>,
+  /// GitHub App installation to authenticate as, preferred over a token
+  #[serde(default)]
+  pub(crate) app: Option<GithubAppConfig>,
+  /// GitHub Enterprise Server instances, addressed by name from the registry and the API
+  #[serde(default)]
+  pub(crate) hosts: Vec<GithubHostConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct GithubHostConfig {
+  /// Short name such as `ghe`, used in registry repos and `/v1/install/{name}/{user}/{repo}`
+  pub(crate) name: String,
+  /// REST API root, `https://<host>/api/v3` on GitHub Enterprise Server
+  pub(crate) api_url: String,
+  /// Web root for links to repos, `https://<host>`
+  pub(crate) web_url: String,
+  /// Personal access token; `GITHUB_TOKEN_<NAME>` is used when unset
+  #[serde(default)]
+  pub(crate) token: Option<String>,
+  /// GitHub App installation on this host, preferred over a token
+  #[serde(default)]
+  pub(crate) app: Option<GithubAppConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct GithubAppConfig {
+  pub(crate) app_id: u64,
+  /// PEM encoded RSA private key generated for the app
+  pub(crate) private_key_path: String,
+  pub(crate) installation_id: u64,
+}
+
+impl GithubConfig {
+  pub(crate) fn token(&self) -> Option<String> {
+    self
+      .token
+      .clone()
+      .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+      .or_else(|| std::env::var("GH_TOKEN").ok())
+      .filter(|token| !token.is_empty())
+  }
+
+  pub(crate) fn host(&self, name: &str) -> Option<&GithubHostConfig> {
+    self.hosts.iter().find(|host| host.name == name)
+  }
+
+  /// Host names share `/install/{host}/...` with the other providers, which live under
+  /// `/install/-/...` and would shadow a host named `-`.
+  fn validate(&self) -> Result<()> {
+    for host in &self.hosts {
+      if host.name == PROVIDER_ROUTE_PREFIX {
+        anyhow::bail!(
+          "github host name '{}' is reserved for the /install/{}/... routes",
+          host.name,
+          PROVIDER_ROUTE_PREFIX
+        );
+      }
+    }
+    Ok(())
+  }
+}
+
+const PROVIDER_ROUTE_PREFIX: &str = "-";
+
+impl GithubHostConfig {
+  pub(crate) fn token(&self) -> Option<String> {
+    let variable = format!(
+      "GITHUB_TOKEN_{}",
+      self.name.to_ascii_uppercase().replace(['-', '.'], "_")
+    );
+    self
+      .token
+      .clone()
+      .or_else(|| std::env::var(variable).ok())
+      .filter(|token| !token.is_empty())
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct RegistryConfig {
+  /// CSV file describing the supported apps, relative to the working directory
+  pub(crate) path: String,
+}
+
+impl Default for RegistryConfig {
+  fn default() -> Self {
+    RegistryConfig {
+      path: "apps.csv".to_string(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct PythonConfig {
+  /// PEP 503/691 simple index used to resolve and install `pip` apps
+  pub(crate) index_url: String,
+  pub(crate) timeout_seconds: u64,
+}
+
+impl Default for PythonConfig {
+  fn default() -> Self {
+    PythonConfig {
+      index_url: "https://pypi.org/simple".to_string(),
+      timeout_seconds: 10,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct GitlabConfig {
+  /// GitLab instance serving `gitlab` apps and `/install/-/gitlab/...`
+  pub(crate) base_url: String,
+  /// Personal, project or group access token; `GITLAB_TOKEN` is used when unset
+  #[serde(default)]
+  pub(crate) token: Option<String>,
+  pub(crate) api_timeout_seconds: u64,
+}
+
+impl Default for GitlabConfig {
+  fn default() -> Self {
+    GitlabConfig {
+      base_url: "https://gitlab.com".to_string(),
+      token: None,
+      api_timeout_seconds: 10,
+    }
+  }
+}
+
+impl GitlabConfig {
+  pub(crate) fn token(&self) -> Option<String> {
+    self
+      .token
+      .clone()
+      .or_else(|| std::env::var("GITLAB_TOKEN").ok())
+      .filter(|token| !token.is_empty())
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ForgejoConfig {
+  /// Gitea/Forgejo hosts accepted by `/install/-/forgejo/{host}/...`
+  pub(crate) allowed_hosts: Vec<String>,
+  pub(crate) api_timeout_seconds: u64,
+}
+
+impl Default for ForgejoConfig {
+  fn default() -> Self {
+    ForgejoConfig {
+      allowed_hosts: vec!["codeberg.org".to_string()],
+      api_timeout_seconds: 10,
+    }
+  }
+}
+
+impl ForgejoConfig {
+  pub(crate) fn is_allowed(&self, host: &str) -> bool {
+    self
+      .allowed_hosts
+      .iter()
+      .any(|allowed| allowed.eq_ignore_ascii_case(host))
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct HashicorpConfig {
+  /// Releases site publishing `{product}/index.json` for `hashicorp` apps
+  pub(crate) base_url: String,
+  pub(crate) timeout_seconds: u64,
+  /// How long a product's release index is reused before it is fetched again
+  pub(crate) index_ttl_seconds: u64,
+}
+
+impl Default for HashicorpConfig {
+  fn default() -> Self {
+    HashicorpConfig {
+      base_url: "https://releases.hashicorp.com".to_string(),
+      timeout_seconds: 30,
+      index_ttl_seconds: 600,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CargoConfig {
+  /// Sparse crate index used to resolve crate versions for `method=cargo`
+  pub(crate) index_url: String,
+  pub(crate) timeout_seconds: u64,
+}
+
+impl Default for CargoConfig {
+  fn default() -> Self {
+    CargoConfig {
+      index_url: CRATES_IO_INDEX.to_string(),
+      timeout_seconds: 10,
+    }
+  }
+}
+
+impl CargoConfig {
+  /// The `--index` to hand to cargo, `None` for crates.io which it uses anyway.
+  pub(crate) fn cargo_index(&self) -> Option<String> {
+    let index = self.index_url.trim_end_matches('/');
+    (index != CRATES_IO_INDEX.trim_end_matches('/')).then(|| format!("sparse+{}/", index))
+  }
+}
+
+const CRATES_IO_INDEX: &str = "https://index.crates.io/";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct NpmConfig {
+  /// npm registry used to resolve and install `npm` apps
+  pub(crate) registry_url: String,
+  pub(crate) timeout_seconds: u64,
+}
+
+impl Default for NpmConfig {
+  fn default() -> Self {
+    NpmConfig {
+      registry_url: "https://registry.npmjs.org".to_string(),
+      timeout_seconds: 10,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct OciConfig {
+  /// Registry hosts (`host:port`) spoken to over plain http; loopback registries always are
+  #[serde(default)]
+  pub(crate) insecure_registries: Vec<String>,
+  pub(crate) timeout_seconds: u64,
+  /// Largest image layer downloaded while looking for an app's `archive_path`
+  pub(crate) max_layer_bytes: u64,
+}
+
+impl Default for OciConfig {
+  fn default() -> Self {
+    OciConfig {
+      insecure_registries: vec![],
+      timeout_seconds: 30,
+      max_layer_bytes: 256 * 1024 * 1024,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ChecksumConfig {
+  pub(crate) timeout_seconds: u64,
+  /// Parsed checksum files kept in memory, by URL
+  pub(crate) max_capacity: u64,
+  pub(crate) ttl_seconds: u64,
+}
+
+impl Default for ChecksumConfig {
+  fn default() -> Self {
+    ChecksumConfig {
+      timeout_seconds: 10,
+      max_capacity: 500,
+      ttl_seconds: 3600,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct ScoringConfig {
+  /// Weights used to rank the assets of apps without their own
+  #[serde(default)]
+  pub(crate) weights: ScoreWeights,
+  /// Changes to `weights` by app shortname; fields left out keep their value from `weights`
+  #[serde(default)]
+  pub(crate) apps: HashMap<String, ScoreWeightOverrides>,
+}
+
+impl ScoringConfig {
+  /// The global weights with the app's own entry, if any, laid over them.
+  pub(crate) fn weights_for(&self, shortname: &str) -> ScoreWeights {
+    let mut weights = self.weights.clone();
+    let Some(overrides) = self.apps.get(shortname) else {
+      return weights;
+    };
+    let fields = [
+      (&mut weights.exact_os, overrides.exact_os),
+      (&mut weights.exact_arch, overrides.exact_arch),
+      (&mut weights.exact_libc, overrides.exact_libc),
+      (&mut weights.static_build, overrides.static_build),
+      (&mut weights.debug_penalty, overrides.debug_penalty),
+      (&mut weights.universal_arch, overrides.universal_arch),
+      (&mut weights.fallback_penalty, overrides.fallback_penalty),
+    ];
+    for (weight, value) in fields {
+      if let Some(value) = value {
+        *weight = value;
+      }
+    }
+    // an os listed for the app starts from the filetypes the global weights give it
+    for (os, scores) in &overrides.formats {
+      let inherited = weights
+        .formats
+        .get(os)
+        .or_else(|| weights.formats.get("default"))
+        .cloned()
+        .unwrap_or_default();
+      weights
+        .formats
+        .entry(os.clone())
+        .or_insert(inherited)
+        .extend(
+          scores
+            .iter()
+            .map(|(filetype, score)| (filetype.clone(), *score)),
+        );
+    }
+    weights
+  }
+}
+
+/// One app's changes to the global `ScoreWeights`, field by field and filetype by filetype.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct ScoreWeightOverrides {
+  pub(crate) exact_os: Option<i64>,
+  pub(crate) exact_arch: Option<i64>,
+  pub(crate) exact_libc: Option<i64>,
+  pub(crate) static_build: Option<i64>,
+  pub(crate) debug_penalty: Option<i64>,
+  pub(crate) universal_arch: Option<i64>,
+  pub(crate) fallback_penalty: Option<i64>,
+  pub(crate) formats: HashMap<String, HashMap<String, i64>>,
+}
+
+/// Points an asset earns towards its rank among a release's candidates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct ScoreWeights {
+  /// The asset names the requested os
+  pub(crate) exact_os: i64,
+  /// The asset names the requested arch, rather than none at all
+  pub(crate) exact_arch: i64,
+  /// The asset is built for the requested libc, when the request names one
+  pub(crate) exact_libc: i64,
+  /// Statically linked or musl builds, which run regardless of the host's libc
+  pub(crate) static_build: i64,
+  /// Subtracted from debug builds and symbol packages
+  pub(crate) debug_penalty: i64,
+  /// Universal macOS builds, which run on every mac arch but are larger than exact ones
+  pub(crate) universal_arch: i64,
+  /// Subtracted from builds for another arch that the host only runs through emulation
+  pub(crate) fallback_penalty: i64,
+  /// Points by filetype (`binary`, `tar.gz`, `zip`, `msi installer`, ...) for each os, with
+  /// `default` covering the ones not listed
+  pub(crate) formats: HashMap<String, HashMap<String, i64>>,
+}
+
+impl ScoreWeights {
+  /// Points for `filetype` on `os`; unlisted filetypes earn none.
+  pub(crate) fn format(&self, os: &str, filetype: &str) -> i64 {
+    self
+      .formats
+      .get(os)
+      .or_else(|| self.formats.get("default"))
+      .and_then(|formats| formats.get(filetype))
+      .copied()
+      .unwrap_or(0)
+  }
+}
+
+impl Default for ScoreWeights {
+  fn default() -> Self {
+    let formats = |scores: &[(&str, i64)]| -> HashMap<String, i64> {
+      scores
+        .iter()
+        .map(|(filetype, score)| (filetype.to_string(), *score))
+        .collect()
+    };
+    ScoreWeights {
+      exact_os: 100,
+      exact_arch: 100,
+      exact_libc: 50,
+      static_build: 10,
+      debug_penalty: 150,
+      universal_arch: 50,
+      fallback_penalty: 50,
+      formats: HashMap::from([
+        (
+          "default".to_string(),
+          formats(&[
+            ("zip", 30),
+            ("tar.gz", 20),
+            ("tar.xz", 15),
+            ("binary", 10),
+            ("deb installer", 5),
+          ]),
+        ),
+        (
+          "windows".to_string(),
+          formats(&[
+            ("zip", 30),
+            ("tar.gz", 20),
+            ("binary", 15),
+            ("msi installer", 10),
+          ]),
+        ),
+      ]),
+    }
+  }
 }
 
 impl Config {
@@ -39,6 +468,10 @@ impl Config {
       .with_context(|| format!("Failed to read config file: {:?}", path.as_ref()))?;
     let config: Config = serde_yaml::from_str(&content)
       .with_context(|| format!("Failed to parse config file: {:?}", path.as_ref()))?;
+    config
+      .github
+      .validate()
+      .with_context(|| format!("Invalid config file: {:?}", path.as_ref()))?;
     Ok(config)
   }
 
@@ -65,10 +498,127 @@ impl Default for Config {
       },
       github: GithubConfig {
         api_timeout_seconds: 10,
+        token: None,
+        app: None,
+        hosts: vec![],
       },
+      registry: RegistryConfig::default(),
+      python: PythonConfig::default(),
+      gitlab: GitlabConfig::default(),
+      forgejo: ForgejoConfig::default(),
+      hashicorp: HashicorpConfig::default(),
+      cargo: CargoConfig::default(),
+      npm: NpmConfig::default(),
+      oci: OciConfig::default(),
+      checksums: ChecksumConfig::default(),
+      scoring: ScoringConfig::default(),
     }
   }
 }
 
-pub(crate) static CONFIG: LazyLock<Config> =
-  LazyLock::new(|| Config::load_or_default("config.yaml"));
+pub(crate) const CONFIG_PATH: &str = "config.yaml";
+
+/// Current config; `reload_config` swaps in a new one while the server runs.
+pub(crate) static CONFIG: LazyLock<ArcSwap<Config>> =
+  LazyLock::new(|| ArcSwap::from_pointee(Config::load_or_default(CONFIG_PATH)));
+
+/// Re-reads the config file and swaps it in. On error, including GitHub credentials that are
+/// rejected, the current config stays in place.
+pub(crate) fn reload_config<P: AsRef<Path>>(path: P) -> Result<()> {
+  let config = Config::load(path)?;
+  crate::providers::gh::init_client(&config.github).context("GitHub credentials rejected")?;
+  CONFIG.store(Arc::new(config));
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn failed_reload_keeps_the_current_config() {
+    let before = CONFIG.load_full();
+
+    let broken = std::env::temp_dir().join(format!("termlibs-broken-{}.yaml", std::process::id()));
+    fs::write(&broken, "server: [not, a, mapping]\n").unwrap();
+    let err = reload_config(&broken).unwrap_err();
+    fs::remove_file(&broken).unwrap();
+    assert!(err.to_string().contains("Failed to parse config file"));
+
+    let after = CONFIG.load_full();
+    assert!(Arc::ptr_eq(&before, &after));
+  }
+
+  #[test]
+  fn rejected_credentials_keep_the_current_config() {
+    let before = CONFIG.load_full();
+
+    let mut config = Config::default();
+    config.github.app = Some(GithubAppConfig {
+      app_id: 1,
+      private_key_path: "/nonexistent/termlibs-app.pem".to_string(),
+      installation_id: 2,
+    });
+    let path = std::env::temp_dir().join(format!("termlibs-app-{}.yaml", std::process::id()));
+    fs::write(&path, serde_yaml::to_string(&config).unwrap()).unwrap();
+    let err = reload_config(&path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+    assert!(format!("{:#}", err).contains("Failed to read GitHub App private key"));
+
+    let after = CONFIG.load_full();
+    assert!(Arc::ptr_eq(&before, &after));
+  }
+
+  #[test]
+  fn app_weights_are_laid_over_the_global_ones() {
+    let scoring: ScoringConfig = serde_yaml::from_str(
+      r#"
+weights:
+  exact_os: 100
+  static_build: 25
+  formats:
+    default: {"zip": 30, "tar.gz": 20, "binary": 10}
+apps:
+  tool:
+    debug_penalty: 500
+    formats:
+      default: {"binary": 40}
+"#,
+    )
+    .unwrap();
+
+    let tool = scoring.weights_for("tool");
+    assert_eq!(tool.static_build, 25);
+    assert_eq!(tool.debug_penalty, 500);
+    assert_eq!(tool.format("linux", "binary"), 40);
+    assert_eq!(tool.format("linux", "zip"), 30);
+
+    let other = scoring.weights_for("other");
+    assert_eq!(other.debug_penalty, ScoreWeights::default().debug_penalty);
+    assert_eq!(other.format("linux", "binary"), 10);
+  }
+
+  #[test]
+  fn github_hosts_cannot_shadow_static_routes() {
+    let path = std::env::temp_dir().join(format!("termlibs-hosts-{}.yaml", std::process::id()));
+    let host = |name: &str| {
+      let mut config = Config::default();
+      config.github.hosts.push(GithubHostConfig {
+        name: name.to_string(),
+        api_url: "https://git.example/api/v3".to_string(),
+        web_url: "https://git.example".to_string(),
+        token: None,
+        app: None,
+      });
+      serde_yaml::to_string(&config).unwrap()
+    };
+    fs::write(&path, host("-")).unwrap();
+    let err = Config::load(&path).unwrap_err();
+    assert!(format!("{:#}", err).contains("reserved"));
+
+    fs::write(&path, host("ghe")).unwrap();
+    let config = Config::load(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(config.unwrap().github.hosts[0].name, "ghe");
+  }
+}
@@ -23,7 +23,7 @@ impl Target {
 mod tests {
   use super::*;
   use crate::domain::artifact::ArchiveType;
-  use crate::domain::platform::{TargetArch, TargetOs};
+  use crate::domain::platform::{TargetArch, TargetLibc, TargetOs};
 
   struct Itc {
     input: String,
@@ -48,210 +48,163 @@ mod tests {
       Itc::new(
         "yq_darwin_amd64",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Mac,
-            arch: TargetArch::Amd64,
-          },
+          deployment: TargetDeployment::new(TargetOs::Mac, TargetArch::Amd64),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_darwin_amd64.tar.gz",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Mac,
-            arch: TargetArch::Amd64,
-          },
+          deployment: TargetDeployment::new(TargetOs::Mac, TargetArch::Amd64),
           filetype: Filetype::Archive(ArchiveType::TarGz),
         },
       ),
       Itc::new(
         "yq_darwin_arm64",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Mac,
-            arch: TargetArch::Arm64,
-          },
+          deployment: TargetDeployment::new(TargetOs::Mac, TargetArch::Arm64),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_darwin_arm64.tar.gz",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Mac,
-            arch: TargetArch::Arm64,
-          },
+          deployment: TargetDeployment::new(TargetOs::Mac, TargetArch::Arm64),
           filetype: Filetype::Archive(ArchiveType::TarGz),
         },
       ),
       Itc::new(
         "yq_freebsd_386",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Freebsd,
-            arch: TargetArch::x86,
-          },
+          deployment: TargetDeployment::new(TargetOs::Freebsd, TargetArch::x86),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_freebsd_386.tar.gz",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Freebsd,
-            arch: TargetArch::x86,
-          },
+          deployment: TargetDeployment::new(TargetOs::Freebsd, TargetArch::x86),
           filetype: Filetype::Archive(ArchiveType::TarGz),
         },
       ),
       Itc::new(
         "yq_freebsd_amd64",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Freebsd,
-            arch: TargetArch::Amd64,
-          },
+          deployment: TargetDeployment::new(TargetOs::Freebsd, TargetArch::Amd64),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_freebsd_amd64.tar.gz",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Freebsd,
-            arch: TargetArch::Amd64,
-          },
+          deployment: TargetDeployment::new(TargetOs::Freebsd, TargetArch::Amd64),
           filetype: Filetype::Archive(ArchiveType::TarGz),
         },
       ),
       Itc::new(
         "yq_freebsd_arm",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Freebsd,
-            arch: TargetArch::Arm32,
-          },
+          deployment: TargetDeployment::new(TargetOs::Freebsd, TargetArch::Arm32),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_freebsd_arm.tar.gz",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Freebsd,
-            arch: TargetArch::Arm32,
-          },
+          deployment: TargetDeployment::new(TargetOs::Freebsd, TargetArch::Arm32),
           filetype: Filetype::Archive(ArchiveType::TarGz),
         },
       ),
       Itc::new(
         "yq_linux_386",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::x86,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::x86),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_linux_386.tar.gz",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::x86,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::x86),
           filetype: Filetype::Archive(ArchiveType::TarGz),
         },
       ),
       Itc::new(
         "yq_linux_amd64",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::Amd64,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_linux_amd64.tar.gz",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::Amd64,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64),
           filetype: Filetype::Archive(ArchiveType::TarGz),
         },
       ),
       Itc::new(
         "yq_linux_arm",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::Arm32,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Arm32),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_linux_mips",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::Mips,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Mips),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_linux_mips.tar.gz",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::Mips,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Mips),
           filetype: Filetype::Archive(ArchiveType::TarGz),
         },
       ),
       Itc::new(
         "yq_linux_mips64",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::Mips64,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Mips64),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_linux_mips64.tar.gz",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::Mips64,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Mips64),
           filetype: Filetype::Archive(ArchiveType::TarGz),
         },
       ),
       Itc::new(
         "yq_linux_mips64le",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::Mips64Le,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Mips64Le),
           filetype: Filetype::Binary,
         },
       ),
       Itc::new(
         "yq_linux_mips64le.tar.gz",
         Target {
-          deployment: TargetDeployment {
-            os: TargetOs::Linux,
-            arch: TargetArch::Mips64Le,
-          },
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Mips64Le),
+          filetype: Filetype::Archive(ArchiveType::TarGz),
+        },
+      ),
+      Itc::new(
+        "uv-x86_64-unknown-linux-musl.tar.gz",
+        Target {
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64)
+            .with_libc(TargetLibc::Musl),
+          filetype: Filetype::Archive(ArchiveType::TarGz),
+        },
+      ),
+      Itc::new(
+        "uv-armv7-unknown-linux-gnueabihf.tar.gz",
+        Target {
+          deployment: TargetDeployment::new(TargetOs::Linux, TargetArch::Arm32)
+            .with_libc(TargetLibc::Gnu),
           filetype: Filetype::Archive(ArchiveType::TarGz),
         },
       ),
@@ -1,3 +1,4 @@
 pub(crate) mod artifact;
 pub(crate) mod download;
 pub(crate) mod platform;
+pub(crate) mod scoring;
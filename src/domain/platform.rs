@@ -85,6 +85,19 @@ impl TargetOs {
     }
     TargetOs::Unknown
   }
+
+  /// Spelling used by Go-style release names (`GOOS`), the most common convention for assets.
+  pub(crate) fn release_name(&self) -> &'static str {
+    match self {
+      TargetOs::Windows => "windows",
+      TargetOs::Linux => "linux",
+      TargetOs::Mac => "darwin",
+      TargetOs::Freebsd => "freebsd",
+      TargetOs::Openbsd => "openbsd",
+      TargetOs::Netbsd => "netbsd",
+      TargetOs::Unknown => "unknown",
+    }
+  }
 }
 
 impl Display for TargetOs {
@@ -117,6 +130,8 @@ pub(crate) enum TargetArch {
   RiscV,
   #[allow(non_camel_case_types)]
   x86,
+  /// A macOS build that runs on every mac architecture
+  Universal,
   Unknown,
 }
 
@@ -143,12 +158,38 @@ impl Display for TargetArch {
       TargetArch::Mips64Le => write!(f, "mips64le"),
       TargetArch::RiscV => write!(f, "riscv"),
       TargetArch::x86 => write!(f, "x86"),
+      TargetArch::Universal => write!(f, "universal"),
       TargetArch::Unknown => write!(f, "unknown"),
     }
   }
 }
 
 impl TargetArch {
+  /// Spelling used by Go-style release names (`GOARCH`), the most common convention for assets.
+  pub(crate) fn release_name(&self) -> &'static str {
+    match self {
+      TargetArch::Amd64 => "amd64",
+      TargetArch::Arm64 | TargetArch::Aarch64 => "arm64",
+      TargetArch::PPCLe => "ppc64le",
+      TargetArch::PPC => "ppc64",
+      TargetArch::Arm32 => "arm",
+      TargetArch::MipsLe => "mipsle",
+      TargetArch::Mips => "mips",
+      TargetArch::Mips64Le => "mips64le",
+      TargetArch::Mips64 => "mips64",
+      TargetArch::RiscV => "riscv64",
+      TargetArch::x86 => "386",
+      // GoReleaser names universal binaries `darwin_all`
+      TargetArch::Universal => "all",
+      TargetArch::Unknown => "unknown",
+    }
+  }
+
+  /// Whether both name the same architecture, as `arm64` and `aarch64` do.
+  pub(crate) fn same_as(&self, other: &TargetArch) -> bool {
+    self != &TargetArch::Unknown && self.release_name() == other.release_name()
+  }
+
   pub(crate) fn identify(input: &str) -> TargetArch {
     let amd = ["amd64", "x64", "x86_64"];
     let x86 = ["x86", "i386", "i686", "x86_32", "386", "686", "ia32"];
@@ -209,40 +250,153 @@ impl TargetArch {
       return TargetArch::x86;
     }
 
+    // only names without an arch of their own, `tool-all-in-one-linux-amd64` is still amd64
+    let universal = ["universal", "universal2", "all"];
+    let is_universal = input
+      .to_lowercase()
+      .split(|c: char| !c.is_ascii_alphanumeric())
+      .any(|token| universal.contains(&token));
+    if is_universal {
+      return TargetArch::Universal;
+    }
+
     TargetArch::Unknown
   }
 }
 
+/// Builds that run on a host of another architecture: Rosetta 2 runs amd64 on Apple silicon,
+/// Windows on Arm emulates amd64 and 64-bit Windows runs x86 through WOW64. Listed by os, host
+/// arch and build arch.
+const ARCH_FALLBACKS: [(TargetOs, TargetArch, TargetArch); 3] = [
+  (TargetOs::Mac, TargetArch::Arm64, TargetArch::Amd64),
+  (TargetOs::Windows, TargetArch::Arm64, TargetArch::Amd64),
+  (TargetOs::Windows, TargetArch::Amd64, TargetArch::x86),
+];
+
+/// The C library a Linux build links against. `None` is a build that needs neither, such as a
+/// static one.
+#[derive(PartialEq, Debug, Clone, Default, Serialize, ToSchema)]
+pub(crate) enum TargetLibc {
+  Gnu,
+  Musl,
+  #[default]
+  None,
+}
+
+impl_caseless_deserialize!(TargetLibc);
+
+impl From<&str> for TargetLibc {
+  fn from(value: &str) -> Self {
+    TargetLibc::identify(value)
+  }
+}
+
+impl Display for TargetLibc {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      TargetLibc::Gnu => write!(f, "gnu"),
+      TargetLibc::Musl => write!(f, "musl"),
+      TargetLibc::None => write!(f, "none"),
+    }
+  }
+}
+
+impl TargetLibc {
+  pub(crate) fn identify(input: &str) -> TargetLibc {
+    let tokens: Vec<String> = input
+      .to_lowercase()
+      .split(|c: char| !c.is_ascii_alphanumeric())
+      .map(str::to_string)
+      .collect();
+    // target triples append the ABI, e.g. musleabihf and gnueabihf
+    if tokens.iter().any(|token| token.starts_with("musl")) {
+      return TargetLibc::Musl;
+    }
+    if tokens
+      .iter()
+      .any(|token| token.starts_with("gnu") || token == "glibc")
+    {
+      return TargetLibc::Gnu;
+    }
+    TargetLibc::None
+  }
+
+  /// Whether a build for `self` runs on a host with `host`. Only glibc builds need a glibc host.
+  pub(crate) fn runs_on(&self, host: &TargetLibc) -> bool {
+    self != &TargetLibc::Gnu || host == &TargetLibc::Gnu
+  }
+}
+
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub(crate) struct TargetDeployment {
   pub(crate) os: TargetOs,
   pub(crate) arch: TargetArch,
+  /// Unset on builds whose names do not say, and on requests that leave it to the host
+  #[serde(default)]
+  pub(crate) libc: Option<TargetLibc>,
 }
 
 impl Display for TargetDeployment {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    write!(f, "{}-{}", self.os, self.arch)
+    match &self.libc {
+      Some(libc) => write!(f, "{}-{}-{}", self.os, self.arch, libc),
+      None => write!(f, "{}-{}", self.os, self.arch),
+    }
   }
 }
 
 impl TargetDeployment {
   pub(crate) fn new(os: TargetOs, arch: TargetArch) -> TargetDeployment {
-    TargetDeployment { os, arch }
+    TargetDeployment {
+      os,
+      arch,
+      libc: None,
+    }
   }
 
-  pub(crate) fn identify(input: &str) -> TargetDeployment {
+  pub(crate) fn with_libc(self, libc: impl Into<Option<TargetLibc>>) -> TargetDeployment {
     TargetDeployment {
-      os: TargetOs::identify(input),
-      arch: TargetArch::identify(input),
+      libc: libc.into(),
+      ..self
     }
   }
+
+  /// Architectures other than its own whose builds run on this deployment through emulation,
+  /// to fall back to when no native build was published.
+  pub(crate) fn fallback_archs(&self) -> Vec<TargetArch> {
+    ARCH_FALLBACKS
+      .iter()
+      .filter(|(os, host, _)| os == &self.os && host.same_as(&self.arch))
+      .map(|(_, _, build)| build.clone())
+      .collect()
+  }
+
+  /// Whether a build for `arch` only runs here through emulation.
+  pub(crate) fn runs_emulated(&self, arch: &TargetArch) -> bool {
+    self
+      .fallback_archs()
+      .iter()
+      .any(|fallback| fallback.same_as(arch))
+  }
+
+  pub(crate) fn identify(input: &str) -> TargetDeployment {
+    let os = TargetOs::identify(input);
+    // only Linux builds pick a libc
+    let libc = match os {
+      TargetOs::Linux => Some(TargetLibc::identify(input)).filter(|libc| libc != &TargetLibc::None),
+      _ => None,
+    };
+    let arch = match TargetArch::identify(input) {
+      // only macOS builds are universal, elsewhere `all` names no architecture
+      TargetArch::Universal if os != TargetOs::Mac => TargetArch::Unknown,
+      arch => arch,
+    };
+    TargetDeployment { os, arch, libc }
+  }
 }
 
 impl Default for TargetDeployment {
   fn default() -> Self {
-    TargetDeployment {
-      os: TargetOs::Linux,
-      arch: TargetArch::Amd64,
-    }
+    TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64)
   }
 }
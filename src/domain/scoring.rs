@@ -0,0 +1,245 @@
+use crate::config::ScoreWeights;
+use crate::domain::download::Target;
+use crate::domain::platform::{TargetArch, TargetDeployment};
+
+/// Name tokens of builds that do not depend on the host's libc.
+const STATIC_TOKENS: [&str; 2] = ["static", "musl"];
+/// Name tokens of debug builds and symbol packages, which are never what a user wants installed.
+const DEBUG_TOKENS: [&str; 7] = ["debug", "debuginfo", "dbg", "dsym", "pdb", "sym", "symbols"];
+
+/// Whether an asset is built for `wanted` closely enough to be offered: its os must be the
+/// requested one, its arch too unless the name leaves it out, the build is universal or the host
+/// emulates it, and it must run on the requested libc. Builds whose names do not say are taken to
+/// run on any.
+pub(crate) fn is_candidate(target: &Target, wanted: &TargetDeployment) -> bool {
+  target.deployment.os == wanted.os
+    && (target.deployment.arch == TargetArch::Unknown
+      || target.deployment.arch == TargetArch::Universal
+      || target.deployment.arch.same_as(&wanted.arch)
+      || wanted.runs_emulated(&target.deployment.arch))
+    && match (&target.deployment.libc, &wanted.libc) {
+      (Some(libc), Some(host)) => libc.runs_on(host),
+      _ => true,
+    }
+}
+
+/// Whether an asset is only offered because `wanted` emulates its arch.
+pub(crate) fn is_fallback(target: &Target, wanted: &TargetDeployment) -> bool {
+  !target.deployment.arch.same_as(&wanted.arch) && wanted.runs_emulated(&target.deployment.arch)
+}
+
+/// Ranks an asset for `wanted`, higher being better. Ties are left to the caller.
+pub(crate) fn score(
+  name: &str,
+  target: &Target,
+  wanted: &TargetDeployment,
+  weights: &ScoreWeights,
+) -> i64 {
+  let mut score = 0;
+  if target.deployment.os == wanted.os {
+    score += weights.exact_os;
+  }
+  if target.deployment.arch.same_as(&wanted.arch) {
+    score += weights.exact_arch;
+  } else if target.deployment.arch == TargetArch::Universal {
+    score += weights.universal_arch;
+  } else if wanted.runs_emulated(&target.deployment.arch) {
+    score -= weights.fallback_penalty;
+  }
+  if wanted.libc.is_some() && target.deployment.libc == wanted.libc {
+    score += weights.exact_libc;
+  }
+  score += weights.format(&wanted.os.to_string(), &target.filetype.to_string());
+
+  let lower = name.to_ascii_lowercase();
+  let tokens: Vec<&str> = lower
+    .split(|c: char| !c.is_ascii_alphanumeric())
+    .filter(|token| !token.is_empty())
+    .collect();
+  // musl targets come with suffixes such as musleabihf
+  if tokens
+    .iter()
+    .any(|token| STATIC_TOKENS.iter().any(|marker| token.starts_with(marker)))
+  {
+    score += weights.static_build;
+  }
+  if tokens.iter().any(|token| DEBUG_TOKENS.contains(token)) {
+    score -= weights.debug_penalty;
+  }
+  score
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::platform::{TargetLibc, TargetOs};
+
+  fn scored(names: &[&str], wanted: &TargetDeployment, weights: &ScoreWeights) -> Vec<String> {
+    let mut candidates: Vec<(i64, &str)> = names
+      .iter()
+      .map(|name| (Target::identify(name, None), *name))
+      .filter(|(target, _)| is_candidate(target, wanted))
+      .map(|(target, name)| (score(name, &target, wanted, weights), name))
+      .collect();
+    candidates.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(b.1)));
+    candidates
+      .into_iter()
+      .map(|(_, name)| name.to_string())
+      .collect()
+  }
+
+  #[test]
+  fn ranks_exact_static_archives_first() {
+    let names = [
+      "tool-aarch64-unknown-linux-gnu.tar.gz",
+      "tool-x86_64-unknown-linux-gnu.tar.gz",
+      "tool-x86_64-unknown-linux-gnu-debug.tar.gz",
+      "tool-x86_64-unknown-linux-musl.tar.gz",
+      "tool-x86_64-unknown-linux-musl.zip",
+      "tool-linux.tar.gz",
+      "tool-x86_64-pc-windows-msvc.zip",
+      "tool-x86_64-pc-windows-msvc.pdb",
+    ];
+    let weights = ScoreWeights::default();
+
+    let linux = TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64);
+    assert_eq!(
+      scored(&names, &linux, &weights),
+      [
+        "tool-x86_64-unknown-linux-musl.zip",
+        "tool-x86_64-unknown-linux-musl.tar.gz",
+        "tool-x86_64-unknown-linux-gnu.tar.gz",
+        "tool-linux.tar.gz",
+        "tool-x86_64-unknown-linux-gnu-debug.tar.gz",
+      ]
+    );
+
+    // aarch64 is how Rust spells arm64
+    let arm = TargetDeployment::new(TargetOs::Linux, TargetArch::Arm64);
+    assert_eq!(scored(&names, &arm, &weights)[0], names[0]);
+
+    let windows = TargetDeployment::new(TargetOs::Windows, TargetArch::Amd64);
+    assert_eq!(
+      scored(&names, &windows, &weights),
+      [
+        "tool-x86_64-pc-windows-msvc.zip",
+        "tool-x86_64-pc-windows-msvc.pdb"
+      ]
+    );
+  }
+
+  #[test]
+  fn requested_libc_filters_and_ranks() {
+    let names = [
+      "uv-x86_64-unknown-linux-gnu.tar.gz",
+      "uv-x86_64-unknown-linux-musl.tar.gz",
+    ];
+    let weights = ScoreWeights::default();
+    let linux = TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64);
+
+    let musl = linux.clone().with_libc(TargetLibc::Musl);
+    assert_eq!(scored(&names, &musl, &weights), [names[1]]);
+    let gnu = linux.clone().with_libc(TargetLibc::Gnu);
+    assert_eq!(scored(&names, &gnu, &weights), names);
+    // left to the host, the static musl build goes first
+    assert_eq!(scored(&names, &linux, &weights), [names[1], names[0]]);
+    // asking for none leaves only builds that need no glibc
+    let none = linux.clone().with_libc(TargetLibc::None);
+    assert_eq!(scored(&names, &none, &weights), [names[1]]);
+  }
+
+  #[test]
+  fn emulated_builds_are_ranked_last() {
+    let names = [
+      "tool-x86_64-apple-darwin.tar.gz",
+      "tool-aarch64-apple-darwin.tar.gz",
+      "tool-darwin.tar.gz",
+      "tool-x86_64-pc-windows-msvc.zip",
+      "tool-i686-pc-windows-msvc.zip",
+    ];
+    let weights = ScoreWeights::default();
+
+    let mac = TargetDeployment::new(TargetOs::Mac, TargetArch::Arm64);
+    assert_eq!(
+      scored(&names, &mac, &weights),
+      [names[1], names[2], names[0]]
+    );
+    let rosetta = Target::identify(names[0], None);
+    assert!(is_fallback(&rosetta, &mac));
+    assert!(!is_fallback(&Target::identify(names[1], None), &mac));
+
+    let windows_arm = TargetDeployment::new(TargetOs::Windows, TargetArch::Aarch64);
+    assert_eq!(scored(&names, &windows_arm, &weights), [names[3]]);
+    let windows = TargetDeployment::new(TargetOs::Windows, TargetArch::Amd64);
+    assert_eq!(scored(&names, &windows, &weights), [names[3], names[4]]);
+    // an Intel mac runs no arm64 builds
+    let intel_mac = TargetDeployment::new(TargetOs::Mac, TargetArch::Amd64);
+    assert_eq!(scored(&names, &intel_mac, &weights), [names[0], names[2]]);
+  }
+
+  #[test]
+  fn universal_builds_follow_exact_ones() {
+    let names = [
+      "tool-universal-apple-darwin.tar.gz",
+      "tool_darwin_all.tar.gz",
+      "tool_macOS_universal.zip",
+      "tool-aarch64-apple-darwin.tar.gz",
+      "tool-x86_64-apple-darwin.tar.gz",
+      "tool_linux_all.tar.gz",
+    ];
+    for name in &names[..3] {
+      assert_eq!(
+        Target::identify(name, None).deployment.arch,
+        TargetArch::Universal,
+        "{}",
+        name
+      );
+    }
+    assert_eq!(
+      Target::identify(names[5], None).deployment.arch,
+      TargetArch::Unknown
+    );
+
+    let weights = ScoreWeights::default();
+    let arm = TargetDeployment::new(TargetOs::Mac, TargetArch::Aarch64);
+    assert_eq!(
+      scored(&names, &arm, &weights),
+      [names[3], names[2], names[0], names[1], names[4]]
+    );
+    let intel = TargetDeployment::new(TargetOs::Mac, TargetArch::Amd64);
+    assert_eq!(scored(&names, &intel, &weights)[0], names[4]);
+    assert_eq!(scored(&names, &intel, &weights).len(), 4);
+
+    // `all` in a name that also names its arch is not a universal build
+    let bundle = "tool-all-in-one-linux-amd64.tar.gz";
+    assert_eq!(
+      Target::identify(bundle, None).deployment.arch,
+      TargetArch::Amd64
+    );
+    let linux_arm = TargetDeployment::new(TargetOs::Linux, TargetArch::Arm64);
+    assert!(scored(&[bundle], &linux_arm, &weights).is_empty());
+    let linux = TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64);
+    assert_eq!(scored(&[bundle], &linux, &weights), [bundle]);
+  }
+
+  #[test]
+  fn weights_change_the_order() {
+    let names = ["tool_linux_amd64", "tool_linux_amd64.tar.gz"];
+    let linux = TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64);
+    assert_eq!(
+      scored(&names, &linux, &ScoreWeights::default())[0],
+      "tool_linux_amd64.tar.gz"
+    );
+
+    let mut prefer_binaries = ScoreWeights::default();
+    prefer_binaries
+      .formats
+      .get_mut("default")
+      .unwrap()
+      .insert("binary".to_string(), 40);
+    assert_eq!(
+      scored(&names, &linux, &prefer_binaries)[0],
+      "tool_linux_amd64"
+    );
+  }
+}
@@ -8,8 +8,19 @@ use serde_json::to_string;
 pub(crate) enum AppError {
   InvalidInput(String),
   UnsupportedApp(String),
-  NoMatchingAssets { repo: String, target: String },
+  NoMatchingAssets {
+    repo: String,
+    target: String,
+  },
+  /// No assets either, but the repo can be built from source with another `method`
+  NoMatchingAssetsBuildable {
+    repo: String,
+    target: String,
+    method: String,
+  },
+  HostNotAllowed(String),
   UpstreamGithub(String),
+  Upstream(String),
   OctocrabError(String),
   Template(String),
 }
@@ -30,6 +41,8 @@ impl AppError {
       AppError::InvalidInput(_) => StatusCode::BAD_REQUEST,
       AppError::UnsupportedApp(_) => StatusCode::NOT_FOUND,
       AppError::NoMatchingAssets { .. } => StatusCode::NOT_FOUND,
+      AppError::NoMatchingAssetsBuildable { .. } => StatusCode::NOT_FOUND,
+      AppError::HostNotAllowed(_) => StatusCode::FORBIDDEN,
       AppError::UpstreamGithub(message) => {
         if message.to_ascii_lowercase().contains("rate limit") {
           StatusCode::TOO_MANY_REQUESTS
@@ -37,6 +50,7 @@ impl AppError {
           StatusCode::SERVICE_UNAVAILABLE
         }
       }
+      AppError::Upstream(_) => StatusCode::SERVICE_UNAVAILABLE,
       AppError::Template(_) => StatusCode::INTERNAL_SERVER_ERROR,
       AppError::OctocrabError(_) => StatusCode::INTERNAL_SERVER_ERROR,
     }
@@ -47,13 +61,16 @@ impl AppError {
       AppError::InvalidInput(_) => "invalid_input",
       AppError::UnsupportedApp(_) => "unsupported_app",
       AppError::NoMatchingAssets { .. } => "no_matching_assets",
+      AppError::NoMatchingAssetsBuildable { .. } => "no_matching_assets",
+      AppError::HostNotAllowed(_) => "host_not_allowed",
       AppError::UpstreamGithub(_) => "upstream_github_error",
+      AppError::Upstream(_) => "upstream_error",
       AppError::Template(_) => "template_error",
       AppError::OctocrabError(_) => "octocrab_error",
     }
   }
 
-  fn message(&self) -> String {
+  pub(crate) fn message(&self) -> String {
     match self {
       AppError::InvalidInput(message) => message.clone(),
       AppError::UnsupportedApp(app) => format!("Unsupported app: {}", app),
@@ -63,7 +80,19 @@ impl AppError {
           repo, target
         )
       }
+      AppError::NoMatchingAssetsBuildable {
+        repo,
+        target,
+        method,
+      } => {
+        format!(
+          "No matching assets found for '{}' and target '{}', pass method={} to build it from source",
+          repo, target, method
+        )
+      }
+      AppError::HostNotAllowed(host) => format!("Host is not in the allowlist: {}", host),
       AppError::UpstreamGithub(message) => message.clone(),
+      AppError::Upstream(message) => message.clone(),
       AppError::Template(message) => message.clone(),
       AppError::OctocrabError(message) => message.clone(),
     }
@@ -106,3 +135,17 @@ impl From<octocrab::Error> for AppError {
     }
   }
 }
+
+impl From<reqwest::Error> for AppError {
+  fn from(value: reqwest::Error) -> Self {
+    let url = value
+      .url()
+      .map(|url| url.to_string())
+      .unwrap_or("<unknown>".to_string());
+    if value.is_timeout() {
+      Self::Upstream(format!("request to {} timed out", url))
+    } else {
+      Self::Upstream(format!("request to {} failed: {}", url, value))
+    }
+  }
+}
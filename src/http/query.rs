@@ -1,4 +1,4 @@
-use crate::domain::platform::{TargetArch, TargetOs};
+use crate::domain::platform::{TargetArch, TargetLibc, TargetOs};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Map, Value};
 use std::fmt::Display;
@@ -6,8 +6,16 @@ use utoipa::{IntoParams, ToSchema};
 
 #[derive(Debug, PartialEq, Clone, Deserialize, Serialize, ToSchema)]
 pub(crate) enum InstallMethod {
+  #[serde(alias = "installer")]
   Installer,
+  #[serde(alias = "binary")]
   Binary,
+  /// Build from source with `go install`, for Go repos that publish no binaries
+  #[serde(alias = "go")]
+  Go,
+  /// Install the repo's crate with cargo-binstall or `cargo install`
+  #[serde(alias = "cargo")]
+  Cargo,
 }
 
 impl Display for InstallMethod {
@@ -15,6 +23,8 @@ impl Display for InstallMethod {
     match self {
       InstallMethod::Installer => write!(f, "installer"),
       InstallMethod::Binary => write!(f, "binary"),
+      InstallMethod::Go => write!(f, "go"),
+      InstallMethod::Cargo => write!(f, "cargo"),
     }
   }
 }
@@ -23,6 +33,8 @@ impl From<&str> for InstallMethod {
   fn from(value: &str) -> Self {
     match value {
       "installer" => InstallMethod::Installer,
+      "go" => InstallMethod::Go,
+      "cargo" => InstallMethod::Cargo,
       _ => InstallMethod::Binary,
     }
   }
@@ -40,8 +52,12 @@ pub(crate) struct InstallQueryOptions {
   pub(crate) arch: TargetArch,
   #[serde(default = "default_os")]
   pub(crate) os: TargetOs,
+  /// C library of Linux builds, `none` for builds that need neither; left to the script to
+  /// detect when not given
+  #[serde(default)]
+  pub(crate) libc: Option<TargetLibc>,
   #[serde(default = "default_method")]
-  method: InstallMethod,
+  pub(crate) method: InstallMethod,
   #[serde(default = "default_download_only")]
   download_only: bool,
   #[serde(default = "default_force")]
@@ -52,10 +68,24 @@ pub(crate) struct InstallQueryOptions {
   pub(crate) log_level: String,
   #[serde(default = "default_inline")]
   pub(crate) inline: bool,
+  /// Install the best ranked asset instead of asking when several suit the target
+  #[serde(default)]
+  pub(crate) auto_select: bool,
 }
 
+/// Query of `/checksums/{app}`.
+#[derive(Debug, Deserialize, IntoParams)]
+pub(crate) struct ChecksumQuery {
+  /// App version, default is latest
+  #[serde(default = "default_latest")]
+  pub(crate) version: String,
+}
+
+/// Version installed when a request does not ask for one.
+pub(crate) const DEFAULT_VERSION: &str = "latest";
+
 fn default_latest() -> String {
-  "latest".to_string()
+  DEFAULT_VERSION.to_string()
 }
 
 fn default_prefix() -> String {
@@ -115,12 +145,14 @@ impl InstallQueryOptions {
       prefix: prefix.unwrap_or_else(default_prefix),
       arch: arch.unwrap_or_else(default_arch),
       os: os.unwrap_or_else(default_os),
+      libc: None,
       method: method.unwrap_or_else(default_method),
       download_only: download_only.unwrap_or_else(default_download_only),
       force: force.unwrap_or_else(default_force),
       quiet: quiet.unwrap_or_else(default_quiet),
       log_level: log_level.unwrap_or_else(default_log_level),
       inline: inline.unwrap_or_else(default_inline),
+      auto_select: false,
     }
   }
 
@@ -135,12 +167,14 @@ impl InstallQueryOptions {
         "prefix": self.prefix.as_str(),
         "arch": self.arch.to_string(),
         "os": self.os.to_string(),
+        "libc": self.libc.as_ref().map(ToString::to_string).unwrap_or_default(),
         "method": self.method.to_string(),
         "download_only": self.download_only,
         "force": self.force,
         "quiet": self.quiet,
         "log_level": self.log_level.as_str(),
         "inline": self.inline,
+        "auto_select": self.auto_select,
     })
     .as_object()
     .unwrap()
@@ -1,6 +1,7 @@
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use tera::escape_html;
 use utoipa::ToSchema;
 
@@ -67,6 +68,39 @@ impl ScriptResponse {
   }
 }
 
+/// Catalog entry describing one app accepted by `/install/{app}`.
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct AppInfo {
+  /// Name used in `/install/{app}`
+  pub(crate) shortname: String,
+  /// Where releases come from: github, gitlab, forgejo, hashicorp, url, pip, npm or oci
+  pub(crate) source: String,
+  /// Project, download host or package index page
+  pub(crate) repo_url: String,
+  pub(crate) description: Option<String>,
+  /// os/arch combinations that can be requested
+  pub(crate) platforms: Vec<AppPlatform>,
+  /// Version installed when `version` is not given
+  pub(crate) default_version: String,
+}
+
+/// An `os`/`arch` pair, spelled as the install query parameters expect.
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct AppPlatform {
+  pub(crate) os: String,
+  pub(crate) arch: String,
+}
+
+/// SHA-256 digests published for one version of an app.
+#[derive(Debug, Serialize, ToSchema)]
+pub(crate) struct AppChecksums {
+  pub(crate) shortname: String,
+  /// The resolved version, never `latest`
+  pub(crate) version: String,
+  /// Hex digests by file name
+  pub(crate) checksums: BTreeMap<String, String>,
+}
+
 impl IntoResponse for ScriptResponse {
   fn into_response(self) -> Response {
     let content_type = if self.html {
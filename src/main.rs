@@ -10,7 +10,7 @@ use axum::{
   middleware::{self, Next},
   response::{Html, IntoResponse, Redirect},
   routing::get,
-  Router,
+  Json, Router,
 };
 use std::net::SocketAddr;
 use tower_http::cors::CorsLayer;
@@ -22,17 +22,18 @@ mod domain;
 mod error;
 mod http;
 mod providers;
+mod reload;
 mod services;
 mod static_site;
 mod supported_apps;
 mod templates;
 
-use crate::cli::{CliInstallOutput, Commands, ScriptCommands};
-use crate::domain::platform::{TargetArch, TargetOs};
+use crate::cli::{CliInstallOutput, Commands, RegistryCommands, ScriptCommands};
+use crate::domain::platform::{TargetArch, TargetLibc, TargetOs};
 use crate::error::AppError;
-use crate::http::query::{InstallMethod, InstallQueryOptions};
-use crate::http::responses::ScriptResponse;
-use crate::services::installer;
+use crate::http::query::{ChecksumQuery, InstallMethod, InstallQueryOptions};
+use crate::http::responses::{AppChecksums, AppInfo, AppPlatform, ScriptResponse};
+use crate::services::{catalog, installer};
 use crate::templates::TEMPLATES;
 use clap_complete::generate;
 use log::{debug, info, warn};
@@ -90,7 +91,8 @@ async fn favicon() -> impl IntoResponse {
   params(
     ("user" = String, Path, description = "GitHub username"),
     ("repo" = String, Path, description = "GitHub repository name"),
-    ("inline" = Option<bool>, Query, description = "Return script as browser-friendly plain text when true", nullable)
+    ("inline" = Option<bool>, Query, description = "Return script as browser-friendly plain text when true", nullable),
+    ("auto_select" = Option<bool>, Query, description = "Install the best ranked asset instead of prompting when several match", nullable)
   ),
   responses(
     (status = 200, description = "Install script (bash)for arbitrary GitHub repository", body = ScriptResponse, content_type = "application/x-sh"),
@@ -111,6 +113,101 @@ async fn install_arbitrary_github_handler(
     .await
 }
 
+#[utoipa::path(
+  get,
+  path = "/install/{host}/{user}/{repo}",
+  params(
+    ("host" = String, Path, description = "Name of a GitHub Enterprise Server host configured under github.hosts"),
+    ("user" = String, Path, description = "Repository owner"),
+    ("repo" = String, Path, description = "Repository name"),
+    ("inline" = Option<bool>, Query, description = "Return script as browser-friendly plain text when true", nullable),
+    ("auto_select" = Option<bool>, Query, description = "Install the best ranked asset instead of prompting when several match", nullable)
+  ),
+  responses(
+    (status = 200, description = "Install script (bash) for a GitHub Enterprise Server repository", body = ScriptResponse, content_type = "application/x-sh"),
+    (status = 200, description = "Install script (powershell) for a GitHub Enterprise Server repository", body = ScriptResponse, content_type = "application/x-powershell"),
+    (status = 403, description = "The host is not configured")
+  ),
+  tag = "install"
+)]
+async fn install_github_enterprise_handler(
+  Path((host, user, repo)): Path<(String, String, String)>,
+  Query(mut q): Query<InstallQueryOptions>,
+  headers: HeaderMap,
+) -> Result<ScriptResponse, AppError> {
+  debug!(
+    "install_github_enterprise_handler({:?}, {:?}, {:?}) with {:#?}",
+    host, user, repo, q
+  );
+  installer::build_github_enterprise_install_script(
+    &host,
+    &user,
+    &repo,
+    &mut q,
+    accepts_html(&headers),
+  )
+  .await
+}
+
+#[utoipa::path(
+  get,
+  path = "/install/-/gitlab/{project}",
+  params(
+    ("project" = String, Path, description = "GitLab project path, nested groups allowed (e.g., group/subgroup/project)"),
+    ("inline" = Option<bool>, Query, description = "Return script as browser-friendly plain text when true", nullable),
+    ("auto_select" = Option<bool>, Query, description = "Install the best ranked asset instead of prompting when several match", nullable)
+  ),
+  responses(
+    (status = 200, description = "Install script (bash) for a GitLab project", body = ScriptResponse, content_type = "application/x-sh"),
+    (status = 200, description = "Install script (powershell) for a GitLab project", body = ScriptResponse, content_type = "application/x-powershell")
+  ),
+  tag = "install"
+)]
+async fn install_gitlab_handler(
+  Path(project): Path<String>,
+  Query(mut q): Query<InstallQueryOptions>,
+  headers: HeaderMap,
+) -> Result<ScriptResponse, AppError> {
+  debug!("install_gitlab_handler({:?}) with {:#?}", project, q);
+  installer::build_arbitrary_gitlab_install_script(&project, &mut q, accepts_html(&headers)).await
+}
+
+#[utoipa::path(
+  get,
+  path = "/install/-/forgejo/{host}/{owner}/{repo}",
+  params(
+    ("host" = String, Path, description = "Gitea/Forgejo host from the configured allowlist (e.g., codeberg.org)"),
+    ("owner" = String, Path, description = "Repository owner"),
+    ("repo" = String, Path, description = "Repository name"),
+    ("inline" = Option<bool>, Query, description = "Return script as browser-friendly plain text when true", nullable),
+    ("auto_select" = Option<bool>, Query, description = "Install the best ranked asset instead of prompting when several match", nullable)
+  ),
+  responses(
+    (status = 200, description = "Install script (bash) for a Gitea/Forgejo repository", body = ScriptResponse, content_type = "application/x-sh"),
+    (status = 200, description = "Install script (powershell) for a Gitea/Forgejo repository", body = ScriptResponse, content_type = "application/x-powershell"),
+    (status = 403, description = "The host is not in the allowlist")
+  ),
+  tag = "install"
+)]
+async fn install_forgejo_handler(
+  Path((host, owner, repo)): Path<(String, String, String)>,
+  Query(mut q): Query<InstallQueryOptions>,
+  headers: HeaderMap,
+) -> Result<ScriptResponse, AppError> {
+  debug!(
+    "install_forgejo_handler({:?}, {:?}, {:?}) with {:#?}",
+    host, owner, repo, q
+  );
+  installer::build_arbitrary_forgejo_install_script(
+    &host,
+    &owner,
+    &repo,
+    &mut q,
+    accepts_html(&headers),
+  )
+  .await
+}
+
 #[utoipa::path(
   get,
   path = "/install/{app}",
@@ -118,9 +215,11 @@ async fn install_arbitrary_github_handler(
     ("app" = String, Path, description = "Application name (e.g., yq, jq, gh)"),
     ("os" = Option<String>, Query, description = "target os"),
     ("arch" = Option<String>, Query, description = "target architecture", nullable),
+    ("libc" = Option<String>, Query, description = "C library of Linux builds: gnu, musl or none for builds that need neither, detected by the script when not given", nullable),
     ("prefix" = Option<String>, Query, description = "install directory", nullable),
     ("version" = Option<String>, Query, description = "app version, default is latest", nullable),
-    ("inline" = Option<bool>, Query, description = "Return script as browser-friendly plain text when true", nullable)
+    ("inline" = Option<bool>, Query, description = "Return script as browser-friendly plain text when true", nullable),
+    ("auto_select" = Option<bool>, Query, description = "Install the best ranked asset instead of prompting when several match", nullable)
   ),
   responses(
     (status = 200, description = "Install script (bash) for the application", body = ScriptResponse, content_type = "application/x-sh"),
@@ -137,6 +236,55 @@ async fn install_handler(
   installer::build_supported_install_script(&app, &mut q, accepts_html(&headers)).await
 }
 
+#[utoipa::path(
+  get,
+  path = "/apps",
+  responses(
+    (status = 200, description = "Every app accepted by /install/{app}", body = Vec<AppInfo>)
+  ),
+  tag = "apps"
+)]
+async fn apps_handler() -> Result<Json<Vec<AppInfo>>, AppError> {
+  Ok(Json(catalog::list_apps()?))
+}
+
+#[utoipa::path(
+  get,
+  path = "/apps/{app}",
+  params(
+    ("app" = String, Path, description = "Application name (e.g., yq, jq, gh)")
+  ),
+  responses(
+    (status = 200, description = "Details for one supported app", body = AppInfo),
+    (status = 404, description = "The app is not in the registry")
+  ),
+  tag = "apps"
+)]
+async fn app_handler(Path(app): Path<String>) -> Result<Json<AppInfo>, AppError> {
+  Ok(Json(catalog::describe_app(&app)?))
+}
+
+#[utoipa::path(
+  get,
+  path = "/checksums/{app}",
+  params(
+    ("app" = String, Path, description = "Application name (e.g., yq, jq, gh)"),
+    ChecksumQuery
+  ),
+  responses(
+    (status = 200, description = "SHA-256 digests published for the version", body = AppChecksums),
+    (status = 404, description = "The app is not in the registry")
+  ),
+  tag = "apps"
+)]
+async fn checksums_handler(
+  Path(app): Path<String>,
+  Query(q): Query<ChecksumQuery>,
+) -> Result<Json<AppChecksums>, AppError> {
+  debug!("checksums_handler({:?}, {:?})", app, q);
+  Ok(Json(catalog::list_checksums(&app, &q.version).await?))
+}
+
 async fn install_latest_redirect(uri: Uri) -> Redirect {
   let path_and_query = uri
     .path_and_query()
@@ -213,20 +361,27 @@ async fn log_requests_middleware(request: Request, next: Next) -> impl IntoRespo
   ),
   paths(
     install_handler,
-    install_arbitrary_github_handler
+    install_arbitrary_github_handler,
+    install_github_enterprise_handler,
+    install_gitlab_handler,
+    install_forgejo_handler,
+    apps_handler,
+    app_handler,
+    checksums_handler
   ),
   components(
-    schemas(InstallQueryOptions, ScriptResponse, InstallMethod, TargetOs, TargetArch)
+    schemas(InstallQueryOptions, ScriptResponse, InstallMethod, TargetOs, TargetArch, TargetLibc, AppInfo, AppPlatform, AppChecksums)
   ),
   tags(
-    (name = "install", description = "Install script generation")
+    (name = "install", description = "Install script generation"),
+    (name = "apps", description = "Catalog of supported apps")
   )
 )]
 struct ApiDoc;
 
 async fn run_server(serve_args: Option<&cli::ServeArgs>) -> anyhow::Result<()> {
   // Load config (also initializes lazy static CONFIG)
-  let config = &config::CONFIG;
+  let config = config::CONFIG.load_full();
 
   let log_level = serve_args
     .and_then(|args| args.log_level().map(|v| v.to_string()))
@@ -234,6 +389,14 @@ async fn run_server(serve_args: Option<&cli::ServeArgs>) -> anyhow::Result<()> {
 
   setup_logger(log_level.as_str()).context("failed to initialize logger")?;
 
+  info!(
+    "app registry loaded from {:?}: {} apps",
+    config.registry.path,
+    supported_apps::ensure_loaded()?
+  );
+
+  providers::gh::init_client(&config.github).context("GitHub credentials rejected")?;
+
   // make sure the templates are loaded early to check for errors
   TEMPLATES.get_template_names().for_each(|name| {
     info!("template loaded: {}", name);
@@ -260,6 +423,7 @@ async fn run_server(serve_args: Option<&cli::ServeArgs>) -> anyhow::Result<()> {
   info!("starting server at {:?}:{}", listen_ip, port);
 
   let app = build_app(log_requests_enabled)?;
+  reload::spawn_watchers();
 
   let addr = format!("{}:{}", listen_ip, port)
     .parse::<SocketAddr>()
@@ -278,6 +442,10 @@ async fn run_server(serve_args: Option<&cli::ServeArgs>) -> anyhow::Result<()> {
 async fn main() -> anyhow::Result<()> {
   let cli = cli::parse();
 
+  if !matches!(cli.command, Some(Commands::Completions(_))) {
+    supported_apps::ensure_loaded().context("failed to load app registry")?;
+  }
+
   match cli.command {
     Some(Commands::Script(script_cmd)) => match script_cmd {
       ScriptCommands::Install(args) => match args.run().await {
@@ -309,6 +477,20 @@ async fn main() -> anyhow::Result<()> {
         std::process::exit(1);
       }
     },
+    Some(Commands::Registry(RegistryCommands::Check(args))) => match args.run().await {
+      Ok(output) => {
+        io::stdout().write_all(output.body.as_bytes())?;
+        if output.regressions > 0 {
+          eprintln!("{} regression(s) found", output.regressions);
+          std::process::exit(1);
+        }
+        Ok(())
+      }
+      Err(err) => {
+        eprintln!("{}", err.to_json());
+        std::process::exit(1);
+      }
+    },
     Some(Commands::Completions(args)) => {
       let mut command = cli::build_command();
       generate(args.shell, &mut command, "termlibs", &mut io::stdout());
@@ -344,13 +526,34 @@ fn build_app(log_requests_enabled: bool) -> anyhow::Result<Router> {
       "/install/{user}/{repo}",
       get(install_arbitrary_github_handler),
     )
-    .route("/install/{app}", get(install_handler));
+    .route(
+      "/install/{host}/{user}/{repo}",
+      get(install_github_enterprise_handler),
+    )
+    .route("/install/-/gitlab/{*project}", get(install_gitlab_handler))
+    .route(
+      "/install/-/forgejo/{host}/{owner}/{repo}",
+      get(install_forgejo_handler),
+    )
+    .route("/install/{app}", get(install_handler))
+    .route("/apps", get(apps_handler))
+    .route("/apps/{app}", get(app_handler))
+    .route("/checksums/{app}", get(checksums_handler));
 
   let mut app = Router::new()
     .route("/", get(root_handler))
     .route("/install", get(install_latest_redirect))
     .route("/install/{app}", get(install_latest_redirect))
     .route("/install/{user}/{repo}", get(install_latest_redirect))
+    .route(
+      "/install/{host}/{user}/{repo}",
+      get(install_latest_redirect),
+    )
+    .route("/install/-/gitlab/{*project}", get(install_latest_redirect))
+    .route(
+      "/install/-/forgejo/{host}/{owner}/{repo}",
+      get(install_latest_redirect),
+    )
     .route("/favicon.ico", get(favicon))
     .nest("/v1", v1_router)
     .merge(SwaggerUi::new("/swagger-ui").url("/openapi.json", ApiDoc::openapi()))
@@ -399,6 +602,91 @@ mod tests {
     response.assert_header("Location", "/v1/install/yutc?arch=amd64");
   }
 
+  #[tokio::test]
+  async fn test_install_gitlab_nested_groups() {
+    let server = test_server().await;
+    let response = server
+      .get("/install/-/gitlab/group/sub/project?os=linux")
+      .await;
+    response.assert_status(StatusCode::TEMPORARY_REDIRECT);
+    response.assert_header(
+      "Location",
+      "/v1/install/-/gitlab/group/sub/project?os=linux",
+    );
+
+    let response = server.get("/v1/install/-/gitlab/project").await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+    let response = server.get("/v1/install/-/gitlab/group/../project").await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+  }
+
+  #[tokio::test]
+  async fn test_provider_routes_leave_github_owners_alone() {
+    let server = test_server().await;
+    // an owner named gitlab is a GitHub owner or a GitHub Enterprise host, never a GitLab project
+    let response = server.get("/v1/install/gitlab/owner/tool").await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let response = server
+      .get("/v1/install/forgejo/codeberg.org/owner/tool")
+      .await;
+    response.assert_status(StatusCode::NOT_FOUND);
+  }
+
+  #[tokio::test]
+  async fn test_install_forgejo_requires_allowed_host() {
+    let server = test_server().await;
+    let response = server
+      .get("/v1/install/-/forgejo/git.example.com/owner/tool")
+      .await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"], "host_not_allowed");
+
+    let response = server
+      .get("/v1/install/-/forgejo/codeberg.org/.owner/tool")
+      .await;
+    response.assert_status(StatusCode::BAD_REQUEST);
+  }
+
+  #[tokio::test]
+  async fn test_install_github_enterprise_requires_configured_host() {
+    let server = test_server().await;
+    let response = server.get("/install/ghe/owner/tool?os=linux").await;
+    response.assert_status(StatusCode::TEMPORARY_REDIRECT);
+    response.assert_header("Location", "/v1/install/ghe/owner/tool?os=linux");
+
+    let response = server.get("/v1/install/ghe/owner/tool").await;
+    response.assert_status(StatusCode::FORBIDDEN);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"], "host_not_allowed");
+  }
+
+  #[tokio::test]
+  async fn test_apps_catalog() {
+    let server = test_server().await;
+    let response = server.get("/v1/apps").await;
+    response.assert_status_ok();
+    let apps: serde_json::Value = response.json();
+    let apps = apps.as_array().unwrap();
+    let yq = apps.iter().find(|app| app["shortname"] == "yq").unwrap();
+    assert_eq!(yq["source"], "github");
+    assert_eq!(yq["repo_url"], "https://github.com/mikefarah/yq");
+    assert_eq!(yq["default_version"], "latest");
+    assert!(yq["platforms"]
+      .as_array()
+      .unwrap()
+      .contains(&serde_json::json!({"os": "mac", "arch": "arm64"})));
+
+    let response = server.get("/v1/apps/helm").await;
+    response.assert_status_ok();
+    let helm: serde_json::Value = response.json();
+    assert_eq!(helm["source"], "url");
+    assert_eq!(helm["repo_url"], "https://get.helm.sh/");
+
+    let response = server.get("/v1/apps/not-an-app").await;
+    response.assert_status(StatusCode::NOT_FOUND);
+  }
+
   #[tokio::test]
   async fn test_not_found_html_page() {
     let server = test_server().await;
@@ -0,0 +1,384 @@
+use crate::config::CONFIG;
+use crate::error::AppError;
+use crate::http::query::DEFAULT_VERSION;
+use crate::providers::compare_versions;
+use crate::providers::gh::get_file;
+use crate::supported_apps::Repo;
+use log::debug;
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::sync::LazyLock;
+use std::time::Duration;
+use url::Url;
+
+static CLIENT: LazyLock<Client> = LazyLock::new(|| {
+  Client::builder()
+    // the crates.io API turns away requests without one
+    .user_agent(concat!(
+      env!("CARGO_PKG_NAME"),
+      "/",
+      env!("CARGO_PKG_VERSION")
+    ))
+    .build()
+    .expect("Failed to build HTTP client")
+});
+
+/// The `[package]` of a repo's root `Cargo.toml`.
+#[derive(Debug, PartialEq)]
+pub(crate) struct CargoPackage {
+  pub(crate) name: String,
+  /// Whether it declares `[package.metadata.binstall]` for prebuilt downloads
+  pub(crate) binstall: bool,
+  /// Whether `name` is the repo name, standing in for a workspace root without `[package]`
+  pub(crate) from_repo_name: bool,
+}
+
+/// A crate version published on the configured index.
+#[derive(Debug, PartialEq)]
+pub(crate) struct CrateRelease {
+  pub(crate) name: String,
+  pub(crate) version: String,
+}
+
+/// One line of a sparse index file.
+#[derive(Debug, Deserialize)]
+struct IndexEntry {
+  vers: String,
+  #[serde(default)]
+  yanked: bool,
+}
+
+/// The `config.json` at the root of a sparse index.
+#[derive(Debug, Deserialize)]
+struct IndexConfig {
+  api: Option<String>,
+}
+
+/// The registry API's answer for `/api/v1/crates/{name}`.
+#[derive(Debug, Deserialize)]
+struct CrateResponse {
+  #[serde(rename = "crate")]
+  krate: CrateMetadata,
+}
+
+#[derive(Debug, Deserialize)]
+struct CrateMetadata {
+  repository: Option<String>,
+}
+
+/// Reads the root `Cargo.toml` of a GitHub repo. Workspace roots without a `[package]` fall back
+/// to the repo name, which is what the crate is usually published as; check that guess with
+/// [`crate_belongs_to`] before using it.
+pub(crate) async fn get_cargo_package(repo: &Repo) -> Result<Option<CargoPackage>, AppError> {
+  let Some(manifest) = get_file(repo, "Cargo.toml", None).await? else {
+    return Ok(None);
+  };
+  let mut package = parse_cargo_manifest(&manifest);
+  if package.name.is_empty() {
+    let repo_string = repo.get_github_repo()?;
+    package.name = repo_string
+      .rsplit('/')
+      .next()
+      .unwrap_or_default()
+      .to_string();
+    package.from_repo_name = true;
+  }
+  Ok(Some(package))
+}
+
+/// Picks `name` and the binstall table out of a manifest. Only the handful of keys needed here
+/// are understood, this is not a TOML parser.
+fn parse_cargo_manifest(manifest: &str) -> CargoPackage {
+  let mut section = String::new();
+  let mut package = CargoPackage {
+    name: String::new(),
+    binstall: false,
+    from_repo_name: false,
+  };
+  for line in manifest.lines().map(str::trim) {
+    if let Some(header) = line.strip_prefix('[') {
+      section = header
+        .trim_end_matches(']')
+        .trim_matches(['[', ']'])
+        .trim()
+        .to_string();
+      package.binstall |= section.starts_with("package.metadata.binstall");
+      continue;
+    }
+    if section != "package" {
+      continue;
+    }
+    if let Some((key, value)) = line.split_once('=') {
+      if key.trim() == "name" {
+        if let Some(name) = value.split(['"', '\'']).nth(1) {
+          package.name = name.to_string();
+        }
+      }
+    }
+  }
+  package
+}
+
+/// Resolves `version` (or `latest`, skipping prereleases) against the crate's file in a sparse
+/// index. `None` when the index has no such crate.
+pub(crate) async fn get_crate_release(
+  index_url: &str,
+  name: &str,
+  version: &str,
+) -> Result<Option<CrateRelease>, AppError> {
+  let Some(entries) = fetch_index_entries(index_url, name).await? else {
+    return Ok(None);
+  };
+  let mut published: Vec<String> = entries
+    .into_iter()
+    .filter(|entry| !entry.yanked)
+    .map(|entry| entry.vers)
+    .collect();
+  published.sort_by(|a, b| compare_versions(b, a));
+
+  let resolved = if version == DEFAULT_VERSION {
+    published.into_iter().find(|version| !version.contains('-'))
+  } else {
+    let wanted = version.trim_start_matches('v');
+    published.into_iter().find(|version| version == wanted)
+  };
+  let version = resolved.ok_or_else(|| AppError::NoMatchingAssets {
+    repo: name.to_string(),
+    target: version.to_string(),
+  })?;
+  debug!("resolved crate {} to version {}", name, version);
+  Ok(Some(CrateRelease {
+    name: name.to_string(),
+    version,
+  }))
+}
+
+/// Whether the crate published as `name` lists `repo` as its `repository`, going by the registry
+/// API the index names in its `config.json`. Indexes without an API cannot tell, so no.
+pub(crate) async fn crate_belongs_to(
+  index_url: &str,
+  name: &str,
+  repo: &Repo,
+) -> Result<bool, AppError> {
+  let index_config: Option<IndexConfig> = get_json(&index_join(index_url, "config.json")?).await?;
+  let Some(api) = index_config.and_then(|config| config.api) else {
+    return Ok(false);
+  };
+  let url = Url::parse(&format!(
+    "{}/api/v1/crates/{}",
+    api.trim_end_matches('/'),
+    name
+  ))
+  .map_err(|err| AppError::InvalidInput(format!("Invalid crate registry URL: {}", err)))?;
+  let Some(response) = get_json::<CrateResponse>(&url).await? else {
+    return Ok(false);
+  };
+  let expected = normalized_repository(repo.html_url()?.as_str());
+  debug!(
+    "crate {} points at {:?}, expecting {}",
+    name, response.krate.repository, expected
+  );
+  Ok(
+    response
+      .krate
+      .repository
+      .is_some_and(|repository| normalized_repository(&repository) == expected),
+  )
+}
+
+/// `https://github.com/Owner/Tool.git/` and `https://github.com/owner/tool` are the same repo.
+fn normalized_repository(url: &str) -> String {
+  url
+    .trim()
+    .trim_end_matches('/')
+    .trim_end_matches(".git")
+    .to_ascii_lowercase()
+}
+
+fn index_join(index_url: &str, path: &str) -> Result<Url, AppError> {
+  Url::parse(&format!("{}/", index_url.trim_end_matches('/')))
+    .and_then(|index| index.join(path))
+    .map_err(|err| AppError::InvalidInput(format!("Invalid crate index URL: {}", err)))
+}
+
+/// Fetches a JSON document, `None` when it does not exist.
+async fn get_json<T: DeserializeOwned>(url: &Url) -> Result<Option<T>, AppError> {
+  let response = CLIENT
+    .get(url.clone())
+    .timeout(request_timeout())
+    .send()
+    .await?;
+  if response.status() == StatusCode::NOT_FOUND {
+    debug!("{} returned status {}", url, response.status());
+    return Ok(None);
+  }
+  if !response.status().is_success() {
+    return Err(AppError::Upstream(format!(
+      "{} returned status {}",
+      url,
+      response.status()
+    )));
+  }
+  let body = response.text().await?;
+  serde_json::from_str(&body)
+    .map(Some)
+    .map_err(|err| AppError::Upstream(format!("invalid response from {}: {}", url, err)))
+}
+
+async fn fetch_index_entries(
+  index_url: &str,
+  name: &str,
+) -> Result<Option<Vec<IndexEntry>>, AppError> {
+  let url = index_join(index_url, &index_path(name))?;
+  let response = CLIENT
+    .get(url.clone())
+    .timeout(request_timeout())
+    .send()
+    .await?;
+  if response.status() == StatusCode::NOT_FOUND {
+    debug!("{} returned status {}", url, response.status());
+    return Ok(None);
+  }
+  if !response.status().is_success() {
+    return Err(AppError::Upstream(format!(
+      "{} returned status {}",
+      url,
+      response.status()
+    )));
+  }
+  let body = response.text().await?;
+  let entries = body
+    .lines()
+    .filter(|line| !line.trim().is_empty())
+    .map(serde_json::from_str)
+    .collect::<Result<Vec<IndexEntry>, _>>()
+    .map_err(|err| AppError::Upstream(format!("invalid index file {}: {}", url, err)))?;
+  Ok(Some(entries))
+}
+
+/// Location of a crate's file in the index: `1/a`, `2/ab`, `3/a/abc`, `se/rd/serde`.
+fn index_path(name: &str) -> String {
+  let name = name.to_ascii_lowercase();
+  match name.len() {
+    1 => format!("1/{}", name),
+    2 => format!("2/{}", name),
+    3 => format!("3/{}/{}", &name[..1], name),
+    _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+  }
+}
+
+/// Read on every request, so a reloaded `cargo.timeout_seconds` applies right away.
+fn request_timeout() -> Duration {
+  Duration::from_secs(CONFIG.load().cargo.timeout_seconds)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::providers::test_support::serve_with;
+  use axum::routing::get;
+  use axum::Router;
+
+  const INDEX_FILE: &str = r#"{"name":"ripgrep","vers":"13.0.0","deps":[],"cksum":"aa","features":{},"yanked":false}
+{"name":"ripgrep","vers":"14.1.0","deps":[],"cksum":"bb","features":{},"yanked":false}
+{"name":"ripgrep","vers":"14.1.1","deps":[],"cksum":"cc","features":{},"yanked":true}
+{"name":"ripgrep","vers":"15.0.0-rc.1","deps":[],"cksum":"dd","features":{},"yanked":false}
+"#;
+
+  /// A sparse index holding just `ripgrep`, standing in for index.crates.io. The registry API
+  /// behind it knows `ripgrep` and a `tool` crate from somebody else's repo.
+  async fn stub_index() -> String {
+    let base = serve_with(|base| {
+      let config = format!(r#"{{"dl": "{}/dl", "api": "{}"}}"#, base, base);
+      Router::new()
+        .route("/config.json", get(|| async move { config }))
+        .route("/ri/pg/ripgrep", get(|| async { INDEX_FILE }))
+        .route(
+          "/api/v1/crates/ripgrep",
+          get(|| async {
+            r#"{"crate": {"name": "ripgrep", "repository": "https://github.com/BurntSushi/ripgrep.git"}}"#
+          }),
+        )
+        .route(
+          "/api/v1/crates/tool",
+          get(|| async {
+            r#"{"crate": {"name": "tool", "repository": "https://github.com/someone-else/tool"}}"#
+          }),
+        )
+    })
+    .await;
+    format!("{}/", base)
+  }
+
+  #[test]
+  fn index_paths_follow_the_name_length() {
+    assert_eq!(index_path("a"), "1/a");
+    assert_eq!(index_path("xz"), "2/xz");
+    assert_eq!(index_path("bat"), "3/b/bat");
+    assert_eq!(index_path("RipGrep"), "ri/pg/ripgrep");
+  }
+
+  #[test]
+  fn manifests_name_their_package() {
+    let manifest = r#"
+[package]
+name = "tool-cli" # published name
+version = "0.3.0"
+
+[dependencies]
+name = "not-this-one"
+
+[package.metadata.binstall]
+pkg-url = "{ repo }/releases/download/v{ version }/{ name }-{ target }.tar.gz"
+"#;
+    assert_eq!(
+      parse_cargo_manifest(manifest),
+      CargoPackage {
+        name: "tool-cli".to_string(),
+        binstall: true,
+        from_repo_name: false,
+      }
+    );
+
+    let workspace = "[workspace]\nmembers = [\"crates/*\"]\n";
+    assert_eq!(parse_cargo_manifest(workspace).name, "");
+    assert!(!parse_cargo_manifest(workspace).binstall);
+  }
+
+  #[tokio::test]
+  async fn resolves_versions_from_a_sparse_index() {
+    let index = stub_index().await;
+
+    let latest = get_crate_release(&index, "ripgrep", "latest")
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(latest.version, "14.1.0");
+    let pinned = get_crate_release(&index, "ripgrep", "v13.0.0")
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(pinned.version, "13.0.0");
+
+    let yanked = get_crate_release(&index, "ripgrep", "14.1.1")
+      .await
+      .unwrap_err();
+    assert!(matches!(yanked, AppError::NoMatchingAssets { .. }));
+    let unknown = get_crate_release(&index, "unknown-crate", "latest").await;
+    assert_eq!(unknown.unwrap(), None);
+  }
+
+  #[tokio::test]
+  async fn repo_names_only_stand_in_for_crates_of_that_repo() {
+    let index = stub_index().await;
+    let ripgrep = Repo::github("burntsushi/ripgrep");
+    assert!(crate_belongs_to(&index, "ripgrep", &ripgrep).await.unwrap());
+
+    let tool = Repo::github("acme/tool");
+    assert!(!crate_belongs_to(&index, "tool", &tool).await.unwrap());
+    assert!(!crate_belongs_to(&index, "unpublished", &tool)
+      .await
+      .unwrap());
+  }
+}
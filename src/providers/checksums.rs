@@ -0,0 +1,228 @@
+use crate::config::CONFIG;
+use crate::supported_apps::DownloadInfo;
+use log::{debug, warn};
+use moka::future::Cache;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use url::Url;
+
+static CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+
+// Published checksum files do not change, so they are kept by URL
+static CHECKSUM_CACHE: LazyLock<Cache<Url, Arc<HashMap<String, String>>>> = LazyLock::new(|| {
+  Cache::builder()
+    .max_capacity(CONFIG.load().checksums.max_capacity)
+    .time_to_live(Duration::from_secs(CONFIG.load().checksums.ttl_seconds))
+    .build()
+});
+
+/// Per-asset digest files, named after the file they cover.
+const ASSET_SUFFIXES: [&str; 2] = [".sha256", ".sha256sum"];
+
+/// A checksum file published next to the assets of a release.
+#[derive(Debug, Clone)]
+pub(crate) struct ChecksumFile {
+  name: String,
+  url: Url,
+}
+
+impl ChecksumFile {
+  /// The asset a per-asset file such as `tool.tar.gz.sha256` covers, `None` for aggregated
+  /// files such as `checksums.txt`.
+  fn covers(&self) -> Option<&str> {
+    ASSET_SUFFIXES
+      .iter()
+      .find_map(|suffix| self.name.strip_suffix(suffix))
+  }
+}
+
+/// Whether `name` looks like a file of SHA-256 digests rather than an installable asset.
+pub(crate) fn is_checksum_file(name: &str) -> bool {
+  let lower = name.to_ascii_lowercase();
+  ASSET_SUFFIXES.iter().any(|suffix| lower.ends_with(suffix))
+    || lower.contains("checksums")
+    || lower.contains("sha256sums")
+}
+
+/// The checksum files among a release's assets. Taken before the assets are narrowed to a
+/// target, which drops them.
+pub(crate) fn checksum_files(assets: &[DownloadInfo]) -> Vec<ChecksumFile> {
+  assets
+    .iter()
+    .filter(|asset| is_checksum_file(&asset.name))
+    .map(|asset| ChecksumFile {
+      name: asset.name.clone(),
+      url: asset.url.clone(),
+    })
+    .collect()
+}
+
+/// Gives each of `assets` the digest published for it, preferring its own `.sha256` file over
+/// aggregated ones. Installs still work without digests, so unreadable files are only logged.
+pub(crate) async fn attach_checksums(assets: &mut [DownloadInfo], files: &[ChecksumFile]) {
+  if assets.is_empty() || files.is_empty() {
+    return;
+  }
+  let (per_asset, aggregated): (Vec<&ChecksumFile>, Vec<&ChecksumFile>) =
+    files.iter().partition(|file| file.covers().is_some());
+
+  let mut pending = vec![];
+  for asset in assets.iter_mut() {
+    let own = per_asset
+      .iter()
+      .find(|file| file.covers() == Some(asset.name.as_str()));
+    match own {
+      Some(file) => asset.sha256 = fetch_checksums(file).await.get(&asset.name).cloned(),
+      None => pending.push(asset),
+    }
+  }
+  if pending.is_empty() {
+    return;
+  }
+
+  let mut digests = HashMap::new();
+  for file in aggregated {
+    digests.extend(
+      fetch_checksums(file)
+        .await
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone())),
+    );
+  }
+  for asset in pending {
+    asset.sha256 = digests.get(&asset.name).cloned();
+  }
+}
+
+/// Every digest published for a release, by file name. Per-asset files win over aggregated ones,
+/// as in `attach_checksums`.
+pub(crate) async fn release_checksums(files: &[ChecksumFile]) -> HashMap<String, String> {
+  let mut files: Vec<&ChecksumFile> = files.iter().collect();
+  files.sort_by_key(|file| file.covers().is_some());
+  let mut digests = HashMap::new();
+  for file in files {
+    digests.extend(
+      fetch_checksums(file)
+        .await
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone())),
+    );
+  }
+  digests
+}
+
+async fn fetch_checksums(file: &ChecksumFile) -> Arc<HashMap<String, String>> {
+  if let Some(cached) = CHECKSUM_CACHE.get(&file.url).await {
+    return cached;
+  }
+  debug!("fetching checksums from {}", file.url);
+  let body = match CLIENT
+    .get(file.url.clone())
+    .timeout(Duration::from_secs(CONFIG.load().checksums.timeout_seconds))
+    .send()
+    .await
+  {
+    Ok(response) if response.status().is_success() => response.text().await.ok(),
+    Ok(response) => {
+      warn!("{} returned status {}", file.url, response.status());
+      None
+    }
+    Err(err) => {
+      warn!("failed to fetch {}: {}", file.url, err);
+      None
+    }
+  };
+  let Some(body) = body else {
+    return Arc::default();
+  };
+  let digests = Arc::new(parse_checksums(&body, file.covers()));
+  CHECKSUM_CACHE
+    .insert(file.url.clone(), digests.clone())
+    .await;
+  digests
+}
+
+/// Reads SHA-256 digests by file name from GNU coreutils (`<digest>  <name>`, `<digest> *<name>`)
+/// and BSD (`SHA256 (<name>) = <digest>`) lines. A bare digest, as some per-asset files hold, is
+/// taken to cover `covers`. Lines with any other digest length are skipped.
+pub(crate) fn parse_checksums(body: &str, covers: Option<&str>) -> HashMap<String, String> {
+  let is_sha256 =
+    |digest: &str| digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit());
+  let file_name = |path: &str| path.rsplit('/').next().unwrap_or(path).to_string();
+
+  body
+    .lines()
+    .map(str::trim)
+    .filter_map(|line| {
+      if let Some(bsd) = line.strip_prefix("SHA256 (") {
+        let (name, digest) = bsd.rsplit_once(") = ")?;
+        let digest = digest.trim();
+        return is_sha256(digest).then(|| (file_name(name), digest.to_ascii_lowercase()));
+      }
+      match line.split_once(char::is_whitespace) {
+        Some((digest, name)) => {
+          let name = name.trim_start().trim_start_matches('*');
+          (is_sha256(digest) && !name.is_empty())
+            .then(|| (file_name(name), digest.to_ascii_lowercase()))
+        }
+        None => {
+          let name = covers?;
+          is_sha256(line).then(|| (name.to_string(), line.to_ascii_lowercase()))
+        }
+      }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const DIGEST: &str = "6e3bd5c8ab1f7d2c4e9a0b3f5d7c9e1a2b4d6f8a0c2e4b6d8f0a1c3e5b7d9f1a";
+
+  #[test]
+  fn parses_gnu_bsd_and_bare_digests() {
+    let gnu = format!(
+      "{d}  tool_linux_amd64.tar.gz\n{D} *dist/tool_darwin_arm64.zip\n\nnot a digest  README.md\n",
+      d = DIGEST,
+      D = DIGEST.to_ascii_uppercase()
+    );
+    let parsed = parse_checksums(&gnu, None);
+    assert_eq!(parsed.len(), 2);
+    assert_eq!(parsed["tool_linux_amd64.tar.gz"], DIGEST);
+    assert_eq!(parsed["tool_darwin_arm64.zip"], DIGEST);
+
+    let bsd = format!(
+      "SHA256 (tool_linux_arm64.tar.gz) = {}\nSHA512 (tool_linux_arm64.tar.gz) = abc\n",
+      DIGEST
+    );
+    let parsed = parse_checksums(&bsd, None);
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed["tool_linux_arm64.tar.gz"], DIGEST);
+
+    let bare = format!("{}\n", DIGEST);
+    assert!(parse_checksums(&bare, None).is_empty());
+    assert_eq!(
+      parse_checksums(&bare, Some("tool.tar.gz"))["tool.tar.gz"],
+      DIGEST
+    );
+  }
+
+  #[test]
+  fn recognises_checksum_files() {
+    for name in [
+      "checksums.txt",
+      "tool_1.2.0_checksums.txt",
+      "SHA256SUMS",
+      "tool.tar.gz.sha256",
+      "tool.zip.sha256sum",
+    ] {
+      assert!(is_checksum_file(name), "{}", name);
+    }
+    for name in ["tool.tar.gz", "tool.tar.gz.sig", "tool.zip.sha512"] {
+      assert!(!is_checksum_file(name), "{}", name);
+    }
+  }
+}
@@ -0,0 +1,307 @@
+use crate::config::CONFIG;
+use crate::domain::platform::TargetDeployment;
+use crate::error::AppError;
+use crate::providers::checksums::{checksum_files, release_checksums};
+use crate::providers::{checksummed_target_assets, find_release, ReleaseProvider};
+use crate::supported_apps::{DownloadInfo, Repo, SupportedApp};
+use async_trait::async_trait;
+use log::debug;
+use reqwest::header::ACCEPT;
+use reqwest::{Client, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+use url::Url;
+
+static CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+
+/// A release and all of its attachments, before any filtering for a target.
+#[derive(Debug)]
+pub(crate) struct ForgejoRelease {
+  pub(crate) tag: String,
+  pub(crate) assets: Vec<DownloadInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+  tag_name: String,
+  #[serde(default)]
+  draft: bool,
+  #[serde(default)]
+  prerelease: bool,
+  #[serde(default)]
+  assets: Vec<Attachment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Attachment {
+  name: String,
+  #[serde(default)]
+  size: u64,
+  browser_download_url: String,
+}
+
+/// Releases of a repository on an allowed Forgejo or Gitea host.
+pub(crate) struct ForgejoProvider;
+
+#[async_trait]
+impl ReleaseProvider for ForgejoProvider {
+  async fn list_versions(&self, app: &SupportedApp) -> Result<Vec<String>, AppError> {
+    let releases: Vec<Release> = get_json(&format!("{}/releases?limit=50", app.repo.get_url()?))
+      .await?
+      .unwrap_or_default();
+    Ok(
+      releases
+        .into_iter()
+        .filter(|release| !release.draft && !release.prerelease)
+        .map(|release| app.version_from_tag(&release.tag_name))
+        .collect(),
+    )
+  }
+
+  async fn list_assets(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> Result<Vec<DownloadInfo>, AppError> {
+    let release = find_release(app, version, |tag| async move {
+      get_forgejo_release(&app.repo, &tag).await
+    })
+    .await?;
+    let version = app.version_from_tag(&release.tag);
+    checksummed_target_assets(app, release.assets, &version, target_deployment).await
+  }
+
+  async fn fetch_checksums(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+  ) -> Result<HashMap<String, String>, AppError> {
+    let release = find_release(app, version, |tag| async move {
+      get_forgejo_release(&app.repo, &tag).await
+    })
+    .await?;
+    Ok(release_checksums(&checksum_files(&release.assets)).await)
+  }
+}
+
+/// Reads a release from the Gitea releases API, which Forgejo and Codeberg share.
+pub(crate) async fn get_forgejo_release(
+  repo: &Repo,
+  version: &str,
+) -> Result<ForgejoRelease, AppError> {
+  let repo_url = repo.get_url()?;
+  let url = match version {
+    "latest" => format!("{}/releases/latest", repo_url),
+    tag => format!(
+      "{}/releases/tags/{}",
+      repo_url,
+      url::form_urlencoded::byte_serialize(tag.as_bytes()).collect::<String>()
+    ),
+  };
+  debug!("checking for release '{}' at {}", version, url);
+
+  let release: Release = get_json(&url)
+    .await?
+    .ok_or_else(|| AppError::NoMatchingAssets {
+      repo: repo
+        .html_url()
+        .map(|url| url.to_string())
+        .unwrap_or_default(),
+      target: version.to_string(),
+    })?;
+
+  Ok(ForgejoRelease {
+    tag: release.tag_name,
+    assets: release
+      .assets
+      .into_iter()
+      .filter_map(|attachment| {
+        let url = Url::parse(&attachment.browser_download_url).ok()?;
+        Some(DownloadInfo::from_named_url(
+          attachment.name,
+          url,
+          mime::APPLICATION_OCTET_STREAM,
+          attachment.size,
+        ))
+      })
+      .collect(),
+  })
+}
+
+/// Fetches an API resource, `None` when it does not exist.
+async fn get_json<T: DeserializeOwned>(url: &str) -> Result<Option<T>, AppError> {
+  let response = CLIENT
+    .get(url)
+    .timeout(Duration::from_secs(
+      CONFIG.load().forgejo.api_timeout_seconds,
+    ))
+    .header(ACCEPT, "application/json")
+    .send()
+    .await?;
+  if response.status() == StatusCode::NOT_FOUND {
+    debug!("{} returned status {}", url, response.status());
+    return Ok(None);
+  }
+  if !response.status().is_success() {
+    return Err(AppError::Upstream(format!(
+      "{} returned status {}",
+      url,
+      response.status()
+    )));
+  }
+  let body = response.text().await?;
+  serde_json::from_str(&body)
+    .map(Some)
+    .map_err(|err| AppError::Upstream(format!("invalid response from {}: {}", url, err)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::config::ScoreWeights;
+  use crate::domain::platform::{TargetArch, TargetDeployment, TargetOs};
+  use crate::providers::gh::rank_target_assets;
+  use crate::providers::test_support::{serve, serve_with};
+  use crate::supported_apps::AppSource;
+  use axum::extract::Path;
+  use axum::response::IntoResponse;
+  use axum::routing::get;
+  use axum::Router;
+
+  const RELEASE_JSON: &str = r#"{
+    "id": 1,
+    "tag_name": "v0.4.1",
+    "assets": [
+      {"id": 1, "name": "tool-linux-amd64.tar.gz", "size": 2097152, "browser_download_url": "https://codeberg.org/owner/tool/releases/download/v0.4.1/tool-linux-amd64.tar.gz"},
+      {"id": 2, "name": "tool-linux-arm64.tar.gz", "size": 2097152, "browser_download_url": "https://codeberg.org/owner/tool/releases/download/v0.4.1/tool-linux-arm64.tar.gz"},
+      {"id": 3, "name": "tool-linux-amd64.tar.gz.sha256", "size": 90, "browser_download_url": "https://codeberg.org/owner/tool/releases/download/v0.4.1/tool-linux-amd64.tar.gz.sha256"}
+    ]
+  }"#;
+
+  async fn release_by_tag(Path(tag): Path<String>) -> impl IntoResponse {
+    match tag.as_str() {
+      "v0.4.1" => (StatusCode::OK, RELEASE_JSON),
+      _ => (StatusCode::NOT_FOUND, r#"{"message": "Not Found"}"#),
+    }
+  }
+
+  async fn stub_forgejo() -> String {
+    let router = Router::new()
+      .route(
+        "/api/v1/repos/owner/tool/releases/latest",
+        get(|| async { RELEASE_JSON }),
+      )
+      .route(
+        "/api/v1/repos/owner/tool/releases/tags/{tag}",
+        get(release_by_tag),
+      );
+    serve(router).await
+  }
+
+  #[test]
+  fn repo_urls() {
+    let repo = Repo::forgejo("codeberg.org", "owner", "tool");
+    assert_eq!(
+      repo.get_url().unwrap().as_str(),
+      "https://codeberg.org/api/v1/repos/owner/tool"
+    );
+    assert_eq!(
+      repo.html_url().unwrap().as_str(),
+      "https://codeberg.org/owner/tool"
+    );
+  }
+
+  #[tokio::test]
+  async fn attachments_become_download_links() {
+    let base = stub_forgejo().await;
+    let repo = Repo::Forgejo(format!("{}/api/v1/repos/owner/tool", base));
+    for version in ["latest", "v0.4.1"] {
+      let release = get_forgejo_release(&repo, version).await.unwrap();
+      assert_eq!(release.tag, "v0.4.1");
+      assert_eq!(release.assets.len(), 3);
+      let links = rank_target_assets(
+        release.assets,
+        &TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64),
+        &ScoreWeights::default(),
+      );
+      let names: Vec<&str> = links.iter().map(|link| link.name.as_str()).collect();
+      assert_eq!(names, ["tool-linux-amd64.tar.gz"]);
+    }
+
+    let missing = get_forgejo_release(&repo, "v9.9.9").await.unwrap_err();
+    assert!(matches!(missing, AppError::NoMatchingAssets { .. }));
+  }
+
+  const AMD64_DIGEST: &str = "6e3bd5c8ab1f7d2c4e9a0b3f5d7c9e1a2b4d6f8a0c2e4b6d8f0a1c3e5b7d9f1a";
+  const ARM64_DIGEST: &str = "0b1f2c9a3ec9d83b8a5ae80c3b2ef2a6a8dfb8d8bd0e6f1a4b7f5c2d9e8a7b6c";
+
+  /// A release whose attachments, checksum files included, are served by the stub itself.
+  async fn stub_checksummed_release() -> String {
+    serve_with(|base| {
+      let attachment = |id: u32, name: &str, size: u32| {
+        format!(
+          r#"{{"id": {}, "name": "{}", "size": {}, "browser_download_url": "{}/dl/{}"}}"#,
+          id, name, size, base, name
+        )
+      };
+      let release = format!(
+        r#"{{"id": 2, "tag_name": "v1.0.0", "assets": [{}, {}, {}, {}]}}"#,
+        attachment(1, "tool-linux-amd64.tar.gz", 2097152),
+        attachment(2, "tool-linux-arm64.tar.gz", 2097152),
+        attachment(3, "tool-linux-amd64.tar.gz.sha256", 90),
+        attachment(4, "checksums.txt", 200),
+      );
+      // the aggregated file disagrees for amd64, its own .sha256 file wins
+      let checksums = format!(
+        "{}  tool-linux-amd64.tar.gz\nSHA256 (tool-linux-arm64.tar.gz) = {}\n",
+        ARM64_DIGEST, ARM64_DIGEST
+      );
+      Router::new()
+        .route(
+          "/api/v1/repos/owner/tool/releases/tags/v1.0.0",
+          get(|| async move { release }),
+        )
+        .route(
+          "/dl/tool-linux-amd64.tar.gz.sha256",
+          get(|| async { format!("{}\n", AMD64_DIGEST) }),
+        )
+        .route("/dl/checksums.txt", get(|| async move { checksums }))
+    })
+    .await
+  }
+
+  #[tokio::test]
+  async fn checksum_files_attach_digests() {
+    let base = stub_checksummed_release().await;
+    let app = SupportedApp::new(
+      "tool",
+      Repo::Forgejo(format!("{}/api/v1/repos/owner/tool", base)),
+      AppSource::Forgejo,
+    );
+
+    for (arch, digest) in [
+      (TargetArch::Amd64, AMD64_DIGEST),
+      (TargetArch::Arm64, ARM64_DIGEST),
+    ] {
+      let target = TargetDeployment::new(TargetOs::Linux, arch);
+      let assets = ForgejoProvider
+        .list_assets(&app, "1.0.0", &target)
+        .await
+        .unwrap();
+      assert_eq!(assets.len(), 1);
+      assert_eq!(assets[0].sha256.as_deref(), Some(digest));
+    }
+
+    let checksums = ForgejoProvider
+      .fetch_checksums(&app, "1.0.0")
+      .await
+      .unwrap();
+    assert_eq!(checksums.len(), 2);
+    assert_eq!(checksums["tool-linux-amd64.tar.gz"], AMD64_DIGEST);
+    assert_eq!(checksums["tool-linux-arm64.tar.gz"], ARM64_DIGEST);
+  }
+}
@@ -1,50 +1,213 @@
-use crate::config::CONFIG;
+use crate::config::{GithubAppConfig, GithubConfig, GithubHostConfig, ScoreWeights, CONFIG};
 use crate::domain::platform::TargetDeployment;
+use crate::domain::scoring::is_candidate;
 use crate::error::AppError;
-use crate::supported_apps::{DownloadInfo, Repo};
-use log::debug;
+use crate::http::query::DEFAULT_VERSION;
+use crate::providers::checksums::{checksum_files, release_checksums};
+use crate::providers::{checksummed_target_assets, find_release, rank_assets, ReleaseProvider};
+use crate::supported_apps::{DownloadInfo, Repo, SupportedApp};
+use anyhow::Context;
+use async_trait::async_trait;
+use jsonwebtoken::EncodingKey;
+use log::{debug, info, warn};
 use moka::future::Cache;
-use octocrab::models::repos::Release;
+use octocrab::models::repos::{Content, Release};
+use octocrab::models::{AppId, InstallationId};
 use octocrab::{Octocrab, OctocrabBuilder};
-use std::sync::{Arc, LazyLock};
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::sync::{Arc, LazyLock, Mutex};
 use std::time::Duration;
 
 const MIN_ASSET_SIZE: u64 = 64 * 1024; // arbitrary, may need to change if we start installing scripts
 
-// Cache key: (owner, repo, version)
-type CacheKey = (String, String, String);
+// Cache key: (repo API URL, version), the URL telling github.com and enterprise hosts apart
+type CacheKey = (String, String);
 
 static RELEASE_CACHE: LazyLock<Cache<CacheKey, Release>> = LazyLock::new(|| {
-  let cache_config = &CONFIG.cache.github_releases;
+  let cache_config = CONFIG.load().cache.github_releases.clone();
   Cache::builder()
     .max_capacity(cache_config.max_capacity)
     .time_to_live(Duration::from_secs(cache_config.ttl_seconds))
     .build()
 });
 
-static OCTOCRAB: LazyLock<Arc<Octocrab>> = LazyLock::new(|| {
-  Arc::new(
-    OctocrabBuilder::default()
-      .build()
-      .expect("Failed to build Octocrab client"),
-  )
+// Cache key: (repo API URL, path, git ref)
+type ContentsKey = (String, String, Option<String>);
+
+// Repo files looked up for source installs, kept as long as releases so repeated misses for a
+// repo do not ask GitHub for its Cargo.toml and go.mod again
+static CONTENTS_CACHE: LazyLock<Cache<ContentsKey, Vec<Content>>> = LazyLock::new(|| {
+  let cache_config = CONFIG.load().cache.github_releases.clone();
+  Cache::builder()
+    .max_capacity(cache_config.max_capacity)
+    .time_to_live(Duration::from_secs(cache_config.ttl_seconds))
+    .build()
 });
 
-pub(crate) async fn get_github_download_links(
+/// A client and the credentials it was built with.
+type BuiltClient = (GithubAuth, Arc<Octocrab>);
+
+// The github.com client, replaced when a config reload changes its credentials
+static OCTOCRAB: LazyLock<Mutex<Option<BuiltClient>>> = LazyLock::new(|| Mutex::new(None));
+
+/// A GitHub Enterprise Server client and the host config it was built from.
+type BuiltHostClient = (GithubHostConfig, Arc<Octocrab>);
+
+// Clients for GitHub Enterprise Server hosts by name, built on first use and replaced when a
+// config reload changes the host's URL or credentials
+static HOST_CLIENTS: LazyLock<Mutex<HashMap<String, BuiltHostClient>>> =
+  LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Builds the GitHub clients for a config, at startup and before a reload swaps it in, so
+/// rejected credentials stop it from being used instead of degrading to anonymous access. Hosts
+/// the config no longer lists lose their clients.
+pub(crate) fn init_client(config: &GithubConfig) -> anyhow::Result<()> {
+  github_client(config)?;
+  for host in &config.hosts {
+    host_client(host)?;
+  }
+  HOST_CLIENTS
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .retain(|name, _| config.host(name).is_some());
+  Ok(())
+}
+
+/// The client for the host a repo lives on.
+fn client_for(repo: &Repo) -> Result<Arc<Octocrab>, AppError> {
+  let client = match repo.github_host()? {
+    Some(host) => host_client(&host),
+    None => github_client(&CONFIG.load().github),
+  };
+  client.map_err(|err| AppError::OctocrabError(format!("{:#}", err)))
+}
+
+fn github_client(config: &GithubConfig) -> anyhow::Result<Arc<Octocrab>> {
+  let auth = GithubAuth::from_config(config);
+  let mut current = OCTOCRAB
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  match &*current {
+    Some((built_with, client)) if *built_with == auth => Ok(client.clone()),
+    _ => {
+      let client = Arc::new(build_client(auth.clone(), None, "github.com")?);
+      *current = Some((auth, client.clone()));
+      Ok(client)
+    }
+  }
+}
+
+fn host_client(host: &GithubHostConfig) -> anyhow::Result<Arc<Octocrab>> {
+  let mut clients = HOST_CLIENTS
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+  match clients.get(&host.name) {
+    Some((built_from, client)) if built_from == host => Ok(client.clone()),
+    _ => {
+      let client = Arc::new(build_client(
+        GithubAuth::from_host(host),
+        Some(&host.api_url),
+        &host.name,
+      )?);
+      clients.insert(host.name.clone(), (host.clone(), client.clone()));
+      Ok(client)
+    }
+  }
+}
+
+/// How the GitHub client authenticates, chosen from the config: a GitHub App installation, a
+/// personal token, or anonymous (60 requests an hour).
+#[derive(Debug, Clone, PartialEq)]
+enum GithubAuth {
+  App(GithubAppConfig),
+  Token(String),
+  Anonymous,
+}
+
+impl GithubAuth {
+  fn from_config(config: &GithubConfig) -> GithubAuth {
+    match (&config.app, config.token()) {
+      (Some(app), _) => GithubAuth::App(app.clone()),
+      (None, Some(token)) => GithubAuth::Token(token),
+      (None, None) => GithubAuth::Anonymous,
+    }
+  }
+
+  fn from_host(host: &GithubHostConfig) -> GithubAuth {
+    match (&host.app, host.token()) {
+      (Some(app), _) => GithubAuth::App(app.clone()),
+      (None, Some(token)) => GithubAuth::Token(token),
+      (None, None) => GithubAuth::Anonymous,
+    }
+  }
+}
+
+/// Builds a client for github.com, or for the API root of an enterprise host.
+fn build_client(auth: GithubAuth, api_url: Option<&str>, label: &str) -> anyhow::Result<Octocrab> {
+  let mut builder = OctocrabBuilder::default();
+  if let Some(api_url) = api_url {
+    builder = builder
+      .base_uri(api_url)
+      .with_context(|| format!("Invalid GitHub API URL: {}", api_url))?;
+  }
+  match auth {
+    GithubAuth::App(app) => {
+      let pem = fs::read(&app.private_key_path).with_context(|| {
+        format!(
+          "Failed to read GitHub App private key: {}",
+          app.private_key_path
+        )
+      })?;
+      let key = EncodingKey::from_rsa_pem(&pem)
+        .with_context(|| format!("Invalid GitHub App private key: {}", app.private_key_path))?;
+      // installation tokens are requested and renewed by octocrab as they expire
+      let client = builder
+        .app(AppId(app.app_id), key)
+        .build()?
+        .installation(InstallationId(app.installation_id))?;
+      info!(
+        "GitHub API for {} authenticated as app {} installation {}",
+        label, app.app_id, app.installation_id
+      );
+      Ok(client)
+    }
+    GithubAuth::Token(token) => {
+      info!(
+        "GitHub API for {} authenticated with a personal token",
+        label
+      );
+      Ok(builder.personal_token(token).build()?)
+    }
+    GithubAuth::Anonymous => {
+      warn!(
+        "GitHub API for {} used anonymously, configure a token to raise rate limits",
+        label
+      );
+      Ok(builder.build()?)
+    }
+  }
+}
+
+/// A release and all of its assets, before any filtering for a target.
+#[derive(Debug)]
+pub(crate) struct GithubRelease {
+  pub(crate) tag: String,
+  pub(crate) assets: Vec<DownloadInfo>,
+}
+
+pub(crate) async fn get_github_release(
   repo: &Repo,
-  target_deployment: &TargetDeployment,
   version: &str,
-) -> Result<Vec<DownloadInfo>, AppError> {
+) -> Result<GithubRelease, AppError> {
   let repo_string = repo.get_github_repo()?;
   let (owner, repo_name) = repo_string
     .split_once('/')
     .ok_or_else(|| AppError::InvalidInput(format!("Invalid github repo path: {}", repo_string)))?;
 
-  let cache_key = (
-    owner.to_string(),
-    repo_name.to_string(),
-    version.to_string(),
-  );
+  let repo_url = repo.get_url()?.to_string();
+  let cache_key = (repo_url.clone(), version.to_string());
 
   debug!("checking for release '{}' from {:?}", version, repo_string);
 
@@ -54,29 +217,276 @@ pub(crate) async fn get_github_download_links(
     cached
   } else {
     debug!("cache miss for {}/{} version {}", owner, repo_name, version);
-    let repo = OCTOCRAB.repos(owner, repo_name);
+    let client = client_for(repo)?;
+    let repo = client.repos(owner, repo_name);
     let releases = repo.releases();
 
-    let timeout_secs = CONFIG.github.api_timeout_seconds;
-    let release = tokio::time::timeout(Duration::from_secs(timeout_secs), async {
+    let release = with_timeout(async {
       match version {
         "latest" => releases.get_latest().await,
         _ => releases.get_by_tag(version).await,
       }
     })
+    .await?;
+    let release = match release {
+      Err(octocrab::Error::GitHub { source, .. }) if source.status_code.as_u16() == 404 => {
+        return Err(AppError::NoMatchingAssets {
+          repo: repo_string,
+          target: version.to_string(),
+        });
+      }
+      release => release?,
+    };
+
+    // Store in cache, `latest` under its tag as well so the asset lookup that follows is a hit
+    if version == "latest" {
+      let tag_key = (repo_url, release.tag_name.clone());
+      RELEASE_CACHE.insert(tag_key, release.clone()).await;
+    }
+    RELEASE_CACHE.insert(cache_key, release.clone()).await;
+    release
+  };
+
+  Ok(GithubRelease {
+    tag: release.tag_name.clone(),
+    assets: release
+      .assets
+      .iter()
+      .map(DownloadInfo::from_asset)
+      .collect(),
+  })
+}
+
+/// Whether a GitHub app has a release for `version`, whatever assets it carries. Answered from
+/// the release cache when `load_app` just looked it up.
+pub(crate) async fn has_release(app: &SupportedApp, version: &str) -> Result<bool, AppError> {
+  let release = match GithubProvider.resolve_version(app, version).await {
+    Ok(version) => {
+      find_release(app, &version, |tag| async move {
+        get_github_release(&app.repo, &tag).await
+      })
+      .await
+    }
+    Err(err) => Err(err),
+  };
+  match release {
+    Ok(_) => Ok(true),
+    Err(AppError::NoMatchingAssets { .. }) => Ok(false),
+    Err(err) => Err(err),
+  }
+}
+
+/// Runs a GitHub API call under the configured timeout.
+async fn with_timeout<T>(
+  request: impl Future<Output = octocrab::Result<T>>,
+) -> Result<octocrab::Result<T>, AppError> {
+  let timeout_secs = CONFIG.load().github.api_timeout_seconds;
+  tokio::time::timeout(Duration::from_secs(timeout_secs), request)
     .await
     .map_err(|_| {
       AppError::UpstreamGithub(format!(
         "GitHub API request timed out after {} seconds",
         timeout_secs
       ))
-    })??;
+    })
+}
 
-    // Store in cache
-    RELEASE_CACHE.insert(cache_key, release.clone()).await;
-    release
+/// Releases on github.com and the GitHub Enterprise Server hosts under `github.hosts`.
+pub(crate) struct GithubProvider;
+
+#[async_trait]
+impl ReleaseProvider for GithubProvider {
+  async fn list_versions(&self, app: &SupportedApp) -> Result<Vec<String>, AppError> {
+    let repo_string = app.repo.get_github_repo()?;
+    let (owner, repo_name) = repo_string.split_once('/').ok_or_else(|| {
+      AppError::InvalidInput(format!("Invalid github repo path: {}", repo_string))
+    })?;
+    let client = client_for(&app.repo)?;
+    let page = with_timeout(
+      client
+        .repos(owner, repo_name)
+        .releases()
+        .list()
+        .per_page(100)
+        .send(),
+    )
+    .await??;
+    Ok(
+      page
+        .items
+        .into_iter()
+        .filter(|release| !release.draft && !release.prerelease)
+        .map(|release| app.version_from_tag(&release.tag_name))
+        .collect(),
+    )
+  }
+
+  /// Uses the latest release endpoint, which also warms the cache for `list_assets`.
+  async fn resolve_version(&self, app: &SupportedApp, version: &str) -> Result<String, AppError> {
+    if version != DEFAULT_VERSION {
+      return Ok(app.version_from_tag(version));
+    }
+    let release = get_github_release(&app.repo, version).await?;
+    Ok(app.version_from_tag(&release.tag))
+  }
+
+  async fn list_assets(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> Result<Vec<DownloadInfo>, AppError> {
+    let release = find_release(app, version, |tag| async move {
+      get_github_release(&app.repo, &tag).await
+    })
+    .await?;
+    let version = app.version_from_tag(&release.tag);
+    checksummed_target_assets(app, release.assets, &version, target_deployment).await
+  }
+
+  async fn fetch_checksums(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+  ) -> Result<HashMap<String, String>, AppError> {
+    let release = find_release(app, version, |tag| async move {
+      get_github_release(&app.repo, &tag).await
+    })
+    .await?;
+    Ok(release_checksums(&checksum_files(&release.assets)).await)
+  }
+}
+
+/// A Go module in a GitHub repo, and the main package `go install` builds from it.
+#[derive(Debug, PartialEq)]
+pub(crate) struct GoModule {
+  pub(crate) module: String,
+  pub(crate) package: String,
+}
+
+impl GoModule {
+  /// The executable `go install` writes: the last package element, skipping a `/vN` suffix.
+  pub(crate) fn binary_name(&self) -> &str {
+    let mut elements = self.package.rsplit('/');
+    let last = elements.next().unwrap_or_default();
+    let is_major_suffix =
+      last.len() > 1 && last.starts_with('v') && last[1..].chars().all(|c| c.is_ascii_digit());
+    match is_major_suffix {
+      true => elements.next().unwrap_or(last),
+      false => last,
+    }
+  }
+}
+
+/// Reads `go.mod` at `git_ref`, or on the default branch without one. `None` when the repo is
+/// not a Go module.
+pub(crate) async fn get_go_module(
+  repo: &Repo,
+  git_ref: Option<&str>,
+) -> Result<Option<GoModule>, AppError> {
+  let repo_string = repo.get_github_repo()?;
+  let (owner, repo_name) = repo_string
+    .split_once('/')
+    .ok_or_else(|| AppError::InvalidInput(format!("Invalid github repo path: {}", repo_string)))?;
+
+  let Some(module) = get_file(repo, "go.mod", git_ref)
+    .await?
+    .and_then(|go_mod| parse_go_module(&go_mod))
+  else {
+    return Ok(None);
+  };
+  let commands: Vec<String> = get_contents(repo, owner, repo_name, "cmd", git_ref)
+    .await?
+    .into_iter()
+    .filter(|content| content.r#type == "dir")
+    .map(|content| content.name)
+    .collect();
+  Ok(Some(GoModule {
+    package: main_package(&module, repo_name, &commands),
+    module,
+  }))
+}
+
+/// Text of a file in the repo at `git_ref`, or on the default branch without one.
+pub(crate) async fn get_file(
+  repo: &Repo,
+  path: &str,
+  git_ref: Option<&str>,
+) -> Result<Option<String>, AppError> {
+  let repo_string = repo.get_github_repo()?;
+  let (owner, repo_name) = repo_string
+    .split_once('/')
+    .ok_or_else(|| AppError::InvalidInput(format!("Invalid github repo path: {}", repo_string)))?;
+  let contents = get_contents(repo, owner, repo_name, path, git_ref).await?;
+  Ok(
+    contents
+      .first()
+      .filter(|content| content.r#type == "file")
+      .and_then(|content| content.decoded_content()),
+  )
+}
+
+/// A file, or the entries of a directory; empty when the path does not exist.
+async fn get_contents(
+  repo: &Repo,
+  owner: &str,
+  repo_name: &str,
+  path: &str,
+  git_ref: Option<&str>,
+) -> Result<Vec<Content>, AppError> {
+  let cache_key = (
+    repo.get_url()?.to_string(),
+    path.to_string(),
+    git_ref.map(str::to_string),
+  );
+  if let Some(contents) = CONTENTS_CACHE.get(&cache_key).await {
+    return Ok(contents);
+  }
+  let client = client_for(repo)?;
+  let repos = client.repos(owner, repo_name);
+  let mut request = repos.get_content().path(path);
+  if let Some(git_ref) = git_ref {
+    request = request.r#ref(git_ref);
+  }
+  let contents = match with_timeout(request.send()).await? {
+    Ok(mut contents) => contents.take_items(),
+    Err(octocrab::Error::GitHub { source, .. }) if source.status_code.as_u16() == 404 => vec![],
+    Err(err) => return Err(err.into()),
   };
+  CONTENTS_CACHE.insert(cache_key, contents.clone()).await;
+  Ok(contents)
+}
+
+fn parse_go_module(go_mod: &str) -> Option<String> {
+  go_mod
+    .lines()
+    .find_map(|line| line.trim().strip_prefix("module "))
+    .map(|module| module.trim().trim_matches('"').to_string())
+    .filter(|module| !module.is_empty())
+}
 
+/// `cmd/<repo>` when it exists, the only directory under `cmd/` otherwise, else the module root.
+fn main_package(module: &str, repo_name: &str, commands: &[String]) -> String {
+  let command = match commands {
+    [only] => Some(only.as_str()),
+    _ => commands
+      .iter()
+      .find(|command| command.as_str() == repo_name)
+      .map(String::as_str),
+  };
+  match command {
+    Some(command) => format!("{}/cmd/{}", module, command),
+    None => module.to_string(),
+  }
+}
+
+/// Guesses which assets suit the target from their names, for repos without a `file_pattern`,
+/// and ranks them best first.
+pub(crate) fn rank_target_assets(
+  download_infos: Vec<DownloadInfo>,
+  target_deployment: &TargetDeployment,
+  weights: &ScoreWeights,
+) -> Vec<DownloadInfo> {
   let mut matched = vec![];
   let skippable_extensions = [
     ".asc", ".md5", ".sha1", ".sha256", ".sha512", ".sig", ".txt",
@@ -84,25 +494,20 @@ pub(crate) async fn get_github_download_links(
   let skippable_mimetypes = [
     mime::TEXT_PLAIN, // will probably break the ability to get scripts, so we may remove this
   ];
-  let download_infos: Vec<DownloadInfo> = release
-    .assets
-    .iter()
-    .map(DownloadInfo::from_asset)
-    .collect();
-
   let (name_width, filetype_width, mime_width, size_width, deployment_width) =
     calc_all_widths(&download_infos);
   let col = |value: String, width: usize| format!("{value:<width$.width$}");
 
   for download_info in download_infos {
-    let is_target = &download_info.target.deployment == target_deployment;
+    let is_target = is_candidate(&download_info.target, target_deployment);
     let extension_skippable = skippable_extensions
       .iter()
       .any(|ext| download_info.name.ends_with(ext));
     let mimetype_skippable = skippable_mimetypes
       .iter()
       .any(|mime| download_info.content_type.essence_str() == mime.essence_str());
-    let is_big_enough = download_info.size > MIN_ASSET_SIZE;
+    // a size of 0 is unknown, as for GitLab release links
+    let is_big_enough = download_info.size == 0 || download_info.size > MIN_ASSET_SIZE;
     let name_col = col(format!("{:?}", download_info.name), name_width);
     let filetype_col = col(
       format!("{:?}", download_info.target.filetype.to_string()),
@@ -140,7 +545,7 @@ pub(crate) async fn get_github_download_links(
       );
     }
   }
-  Ok(matched)
+  rank_assets(matched, target_deployment, weights)
 }
 
 fn calc_all_widths(download_infos: &[DownloadInfo]) -> (usize, usize, usize, usize, usize) {
@@ -189,13 +594,84 @@ fn calc_all_widths(download_infos: &[DownloadInfo]) -> (usize, usize, usize, usi
 mod tests {
   use super::*;
   use crate::domain::platform::{TargetArch, TargetOs};
-  use crate::supported_apps::Repo;
+  use crate::providers::hashicorp::HashicorpProvider;
+  use crate::supported_apps::{AppSource, Repo};
   use reqwest::Client;
-  use serde_json::Value;
   use std::sync::{LazyLock, Mutex, MutexGuard};
   use std::time::Duration;
   use tokio::time::sleep;
 
+  #[tokio::test]
+  async fn host_clients_are_replaced_when_the_host_changes() {
+    let mut host = GithubHostConfig {
+      name: "replaced".to_string(),
+      api_url: "https://ghe.example/api/v3".to_string(),
+      web_url: "https://ghe.example".to_string(),
+      token: Some("token".to_string()),
+      app: None,
+    };
+    let first = host_client(&host).unwrap();
+    assert!(Arc::ptr_eq(&first, &host_client(&host).unwrap()));
+
+    host.api_url = "https://ghe2.example/api/v3".to_string();
+    let second = host_client(&host).unwrap();
+    assert!(!Arc::ptr_eq(&first, &second));
+    let clients = HOST_CLIENTS.lock().unwrap();
+    assert_eq!(clients["replaced"].0.api_url, host.api_url);
+  }
+
+  #[test]
+  fn go_modules_name_their_main_package() {
+    let go_mod = "// comment\nmodule github.com/owner/tool/v2\n\ngo 1.22\n";
+    let module = parse_go_module(go_mod).unwrap();
+    assert_eq!(module, "github.com/owner/tool/v2");
+    assert_eq!(parse_go_module("go 1.22\n"), None);
+
+    let commands = ["helper".to_string(), "tool".to_string()];
+    let package = main_package(&module, "tool", &commands);
+    assert_eq!(package, "github.com/owner/tool/v2/cmd/tool");
+    assert_eq!(
+      main_package(&module, "tool", &commands[..1]),
+      "github.com/owner/tool/v2/cmd/helper"
+    );
+
+    let root = GoModule {
+      package: main_package(&module, "tool", &[]),
+      module: module.clone(),
+    };
+    assert_eq!(root.package, module);
+    assert_eq!(root.binary_name(), "tool");
+    let command = GoModule { package, module };
+    assert_eq!(command.binary_name(), "tool");
+  }
+
+  #[test]
+  fn github_app_is_preferred_over_tokens() {
+    let app = GithubAppConfig {
+      app_id: 1,
+      private_key_path: "missing-github-app.pem".to_string(),
+      installation_id: 2,
+    };
+    let config = GithubConfig {
+      api_timeout_seconds: 10,
+      token: Some("ghp_config".to_string()),
+      app: Some(app.clone()),
+      hosts: vec![],
+    };
+    assert_eq!(GithubAuth::from_config(&config), GithubAuth::App(app));
+    let err = build_client(GithubAuth::from_config(&config), None, "github.com").unwrap_err();
+    assert!(format!("{:#}", err).contains("missing-github-app.pem"));
+
+    let config = GithubConfig {
+      app: None,
+      ..config
+    };
+    assert_eq!(
+      GithubAuth::from_config(&config),
+      GithubAuth::Token("ghp_config".to_string())
+    );
+  }
+
   static API_SANITY_TEST_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
 
   fn lock_api_sanity_tests() -> MutexGuard<'static, ()> {
@@ -250,7 +726,9 @@ mod tests {
         let mut links = None;
         let mut last_error: Option<AppError> = None;
         for attempt in 1..=3 {
-          match get_github_download_links(&repo, &deployment, "latest").await {
+          match get_github_release(&repo, "latest").await.map(|release| {
+            rank_target_assets(release.assets, &deployment, &ScoreWeights::default())
+          }) {
             Ok(found_links) => {
               links = Some(found_links);
               break;
@@ -342,37 +820,39 @@ mod tests {
   #[tokio::test]
   async fn sanity_terraform_url_exists() {
     let _guard = lock_api_sanity_tests();
-    let client = Client::new();
-    let checkpoint = client
-      .get("https://checkpoint-api.hashicorp.com/v1/check/terraform")
-      .send()
+    let terraform = SupportedApp::new(
+      "terraform",
+      Repo::hashicorp("terraform"),
+      AppSource::Hashicorp,
+    );
+    let version = HashicorpProvider
+      .resolve_version(&terraform, "latest")
       .await
-      .expect("failed to fetch terraform checkpoint metadata");
-    assert!(checkpoint.status().is_success());
-
-    let body = checkpoint
-      .text()
+      .expect("failed to resolve the latest terraform release");
+    let assets = HashicorpProvider
+      .list_assets(
+        &terraform,
+        &version,
+        &TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64),
+      )
       .await
-      .expect("failed reading terraform checkpoint response body");
-    let payload: Value =
-      serde_json::from_str(&body).expect("failed to parse terraform checkpoint JSON");
-    let version = payload
-      .get("current_version")
-      .and_then(Value::as_str)
-      .expect("terraform checkpoint response missing current_version");
-
-    let url = format!(
-      "https://releases.hashicorp.com/terraform/{version}/terraform_{version}_linux_amd64.zip"
+      .expect("failed to list terraform builds");
+    assert_eq!(assets.len(), 1, "expected one linux/amd64 build");
+    assert!(
+      assets[0].sha256.is_some(),
+      "expected a SHA256SUMS digest for {}",
+      assets[0].name
     );
-    let response = client
-      .head(&url)
+
+    let response = Client::new()
+      .head(assets[0].url.clone())
       .send()
       .await
       .expect("failed to check terraform release file URL");
     assert!(
       response.status().is_success(),
       "expected terraform release file to exist at '{}', got status {}",
-      url,
+      assets[0].url,
       response.status()
     );
   }
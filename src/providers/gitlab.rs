@@ -0,0 +1,436 @@
+use crate::config::{GitlabConfig, CONFIG};
+use crate::domain::platform::TargetDeployment;
+use crate::error::AppError;
+use crate::providers::checksums::{checksum_files, release_checksums};
+use crate::providers::{checksummed_target_assets, find_release, ReleaseProvider};
+use crate::supported_apps::{DownloadInfo, Repo, SupportedApp};
+use async_trait::async_trait;
+use log::debug;
+use reqwest::header::ACCEPT;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+use url::Url;
+
+const TOKEN_HEADER: &str = "PRIVATE-TOKEN";
+
+static CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+
+/// A release and all of its files, from release links and same-version generic packages.
+#[derive(Debug)]
+pub(crate) struct GitlabRelease {
+  pub(crate) tag: String,
+  pub(crate) assets: Vec<DownloadInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+  tag_name: String,
+  #[serde(default)]
+  upcoming_release: bool,
+  #[serde(default)]
+  assets: ReleaseAssets,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ReleaseAssets {
+  #[serde(default)]
+  links: Vec<ReleaseLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseLink {
+  name: String,
+  url: String,
+  #[serde(default)]
+  direct_asset_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+  id: u64,
+  name: String,
+  version: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageFile {
+  file_name: String,
+  #[serde(default)]
+  size: u64,
+}
+
+pub(crate) async fn get_gitlab_release(
+  repo: &Repo,
+  version: &str,
+) -> Result<GitlabRelease, AppError> {
+  let config = CONFIG.load().gitlab.clone();
+  get_gitlab_release_with(&config, repo, version).await
+}
+
+async fn get_gitlab_release_with(
+  config: &GitlabConfig,
+  repo: &Repo,
+  version: &str,
+) -> Result<GitlabRelease, AppError> {
+  let project_url = repo.get_url()?;
+  debug!(
+    "checking for release '{}' from {:?}",
+    version,
+    repo.get_gitlab_project()?
+  );
+
+  let release: Release = match version {
+    "latest" => {
+      get_json(
+        config,
+        &project_url,
+        &["releases", "permalink", "latest"],
+        &[],
+      )
+      .await?
+    }
+    tag => get_json(config, &project_url, &["releases", tag], &[]).await?,
+  }
+  .ok_or_else(|| AppError::NoMatchingAssets {
+    repo: repo.get_gitlab_project().unwrap_or_default(),
+    target: version.to_string(),
+  })?;
+
+  let mut assets: Vec<DownloadInfo> = release
+    .assets
+    .links
+    .into_iter()
+    .filter_map(|link| {
+      let url = link.direct_asset_url.unwrap_or(link.url);
+      let url = Url::parse(&url).ok()?;
+      Some(DownloadInfo::from_named_url(
+        link.name,
+        url,
+        mime::APPLICATION_OCTET_STREAM,
+        0,
+      ))
+    })
+    .collect();
+  for asset in get_generic_package_files(config, &project_url, &release.tag_name).await? {
+    if !assets.iter().any(|known| known.name == asset.name) {
+      assets.push(asset);
+    }
+  }
+
+  Ok(GitlabRelease {
+    tag: release.tag_name,
+    assets,
+  })
+}
+
+/// Releases of a project on GitLab.com or the configured GitLab instance.
+pub(crate) struct GitlabProvider;
+
+#[async_trait]
+impl ReleaseProvider for GitlabProvider {
+  /// Releases come back newest first; upcoming ones are skipped.
+  async fn list_versions(&self, app: &SupportedApp) -> Result<Vec<String>, AppError> {
+    let config = CONFIG.load().gitlab.clone();
+    let releases: Vec<Release> = get_json(
+      &config,
+      &app.repo.get_url()?,
+      &["releases"],
+      &[("per_page", "100")],
+    )
+    .await?
+    .unwrap_or_default();
+    Ok(
+      releases
+        .into_iter()
+        .filter(|release| !release.upcoming_release)
+        .map(|release| app.version_from_tag(&release.tag_name))
+        .collect(),
+    )
+  }
+
+  async fn list_assets(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> Result<Vec<DownloadInfo>, AppError> {
+    let release = find_release(app, version, |tag| async move {
+      get_gitlab_release(&app.repo, &tag).await
+    })
+    .await?;
+    let version = app.version_from_tag(&release.tag);
+    checksummed_target_assets(app, release.assets, &version, target_deployment).await
+  }
+
+  async fn fetch_checksums(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+  ) -> Result<HashMap<String, String>, AppError> {
+    let release = find_release(app, version, |tag| async move {
+      get_gitlab_release(&app.repo, &tag).await
+    })
+    .await?;
+    Ok(release_checksums(&checksum_files(&release.assets)).await)
+  }
+}
+
+/// Files of generic packages published under the release tag, with or without a leading `v`.
+/// Projects without a package registry have none.
+async fn get_generic_package_files(
+  config: &GitlabConfig,
+  project_url: &Url,
+  tag: &str,
+) -> Result<Vec<DownloadInfo>, AppError> {
+  let mut versions = vec![tag];
+  if tag.trim_start_matches('v') != tag {
+    versions.push(tag.trim_start_matches('v'));
+  }
+
+  let mut packages: Vec<Package> = vec![];
+  for version in versions {
+    let found: Vec<Package> = get_json(
+      config,
+      project_url,
+      &["packages"],
+      &[
+        ("package_type", "generic"),
+        ("package_version", version),
+        ("per_page", "100"),
+      ],
+    )
+    .await?
+    .unwrap_or_default();
+    packages.extend(
+      found
+        .into_iter()
+        .filter(|package| package.version == version),
+    );
+  }
+
+  let mut assets = vec![];
+  for package in packages {
+    let files: Vec<PackageFile> = get_json(
+      config,
+      project_url,
+      &["packages", &package.id.to_string(), "package_files"],
+      &[("per_page", "100")],
+    )
+    .await?
+    .unwrap_or_default();
+    for file in files {
+      let url = api_url(
+        project_url,
+        &[
+          "packages",
+          "generic",
+          &package.name,
+          &package.version,
+          &file.file_name,
+        ],
+      )?;
+      assets.push(DownloadInfo::from_named_url(
+        file.file_name,
+        url,
+        mime::APPLICATION_OCTET_STREAM,
+        file.size,
+      ));
+    }
+  }
+  Ok(assets)
+}
+
+/// Appends path segments to the project API URL, keeping the encoded project id intact.
+fn api_url(project_url: &Url, segments: &[&str]) -> Result<Url, AppError> {
+  let encoded: Vec<String> = segments
+    .iter()
+    .map(|segment| url::form_urlencoded::byte_serialize(segment.as_bytes()).collect())
+    .collect();
+  let url = format!(
+    "{}/{}",
+    project_url.as_str().trim_end_matches('/'),
+    encoded.join("/")
+  );
+  Url::parse(&url).map_err(|err| AppError::InvalidInput(format!("Invalid GitLab URL: {}", err)))
+}
+
+fn authorized(config: &GitlabConfig, request: RequestBuilder) -> RequestBuilder {
+  match config.token() {
+    Some(token) => request.header(TOKEN_HEADER, token),
+    None => request,
+  }
+}
+
+/// Fetches a project API resource, `None` when it does not exist.
+async fn get_json<T: DeserializeOwned>(
+  config: &GitlabConfig,
+  project_url: &Url,
+  segments: &[&str],
+  query: &[(&str, &str)],
+) -> Result<Option<T>, AppError> {
+  let mut url = api_url(project_url, segments)?;
+  if !query.is_empty() {
+    url.query_pairs_mut().extend_pairs(query);
+  }
+  let response = authorized(
+    config,
+    CLIENT
+      .get(url.clone())
+      .timeout(Duration::from_secs(config.api_timeout_seconds)),
+  )
+  .header(ACCEPT, "application/json")
+  .send()
+  .await?;
+  if response.status() == StatusCode::NOT_FOUND {
+    debug!("{} returned status {}", url, response.status());
+    return Ok(None);
+  }
+  if !response.status().is_success() {
+    return Err(AppError::Upstream(format!(
+      "{} returned status {}",
+      url,
+      response.status()
+    )));
+  }
+  let body = response.text().await?;
+  serde_json::from_str(&body)
+    .map(Some)
+    .map_err(|err| AppError::Upstream(format!("invalid response from {}: {}", url, err)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::providers::test_support::serve;
+  use axum::extract::{Path, Query};
+  use axum::http::HeaderMap;
+  use axum::response::IntoResponse;
+  use axum::routing::get;
+  use axum::Router;
+  use std::collections::HashMap;
+
+  const PROJECT: &str = "group%2Fsub%2Ftool";
+
+  async fn release(Path((project, tag)): Path<(String, String)>) -> impl IntoResponse {
+    assert_eq!(project, "group/sub/tool");
+    match tag.as_str() {
+      "v1.2.0" => (
+        StatusCode::OK,
+        r#"{
+          "tag_name": "v1.2.0",
+          "assets": {
+            "count": 3,
+            "sources": [{"format": "zip", "url": "https://gitlab.example/group/sub/tool/-/archive/v1.2.0/tool-v1.2.0.zip"}],
+            "links": [
+              {"name": "tool_linux_amd64.tar.gz", "url": "https://gitlab.example/uploads/tool_linux_amd64.tar.gz", "direct_asset_url": "https://gitlab.example/group/sub/tool/-/releases/v1.2.0/downloads/tool_linux_amd64.tar.gz"},
+              {"name": "tool_darwin_arm64.tar.gz", "url": "https://gitlab.example/uploads/tool_darwin_arm64.tar.gz"}
+            ]
+          }
+        }"#,
+      ),
+      _ => (StatusCode::NOT_FOUND, r#"{"message": "404 Not Found"}"#),
+    }
+  }
+
+  async fn packages(
+    headers: HeaderMap,
+    Query(query): Query<HashMap<String, String>>,
+  ) -> impl IntoResponse {
+    assert_eq!(headers.get(TOKEN_HEADER).unwrap(), "secret");
+    assert_eq!(
+      query.get("package_type").map(String::as_str),
+      Some("generic")
+    );
+    match query.get("package_version").map(String::as_str) {
+      Some("1.2.0") => {
+        r#"[{"id": 7, "name": "tool", "version": "1.2.0", "package_type": "generic"}]"#
+      }
+      Some(_) => "[]",
+      None => panic!("generic packages must be filtered by version"),
+    }
+  }
+
+  async fn package_files(Path((_, id)): Path<(String, u64)>) -> impl IntoResponse {
+    assert_eq!(id, 7);
+    r#"[
+      {"id": 1, "file_name": "tool_windows_amd64.zip", "size": 2048},
+      {"id": 2, "file_name": "tool_linux_amd64.tar.gz", "size": 1024}
+    ]"#
+  }
+
+  async fn stub_gitlab() -> String {
+    let router = Router::new()
+      .route(
+        "/api/v4/projects/{project}/releases/permalink/latest",
+        get(|Path(project): Path<String>| release(Path((project, "v1.2.0".to_string())))),
+      )
+      .route("/api/v4/projects/{project}/releases/{tag}", get(release))
+      .route("/api/v4/projects/{project}/packages", get(packages))
+      .route(
+        "/api/v4/projects/{project}/packages/{id}/package_files",
+        get(package_files),
+      );
+    serve(router).await
+  }
+
+  #[test]
+  fn nested_groups_round_trip() {
+    let repo = Repo::gitlab("group/sub/tool");
+    assert_eq!(
+      repo.get_url().unwrap().as_str(),
+      format!("https://gitlab.com/api/v4/projects/{}", PROJECT)
+    );
+    assert_eq!(repo.get_gitlab_project().unwrap(), "group/sub/tool");
+    assert_eq!(
+      repo.html_url().unwrap().as_str(),
+      "https://gitlab.com/group/sub/tool"
+    );
+  }
+
+  #[tokio::test]
+  async fn collects_release_links_and_generic_packages() {
+    let base = stub_gitlab().await;
+    let config = GitlabConfig {
+      token: Some("secret".to_string()),
+      ..GitlabConfig::default()
+    };
+
+    let repo = Repo::Gitlab(format!("{}/api/v4/projects/{}", base, PROJECT));
+    for version in ["latest", "v1.2.0"] {
+      let release = get_gitlab_release_with(&config, &repo, version)
+        .await
+        .unwrap();
+      assert_eq!(release.tag, "v1.2.0");
+      let names: Vec<&str> = release.assets.iter().map(|a| a.name.as_str()).collect();
+      assert_eq!(
+        names,
+        [
+          "tool_linux_amd64.tar.gz",
+          "tool_darwin_arm64.tar.gz",
+          "tool_windows_amd64.zip"
+        ]
+      );
+      assert_eq!(
+        release.assets[0].url.as_str(),
+        "https://gitlab.example/group/sub/tool/-/releases/v1.2.0/downloads/tool_linux_amd64.tar.gz"
+      );
+      assert_eq!(
+        release.assets[2].url.as_str(),
+        format!(
+          "{}/api/v4/projects/{}/packages/generic/tool/1.2.0/tool_windows_amd64.zip",
+          base, PROJECT
+        )
+      );
+      assert_eq!(release.assets[2].size, 2048);
+    }
+
+    let missing = get_gitlab_release_with(&config, &repo, "v9.9.9")
+      .await
+      .unwrap_err();
+    assert!(matches!(missing, AppError::NoMatchingAssets { .. }));
+  }
+}
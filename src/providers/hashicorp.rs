@@ -0,0 +1,291 @@
+use crate::config::CONFIG;
+use crate::domain::platform::TargetDeployment;
+use crate::error::AppError;
+use crate::providers::checksums::parse_checksums;
+use crate::providers::{compare_versions, ReleaseProvider};
+use crate::supported_apps::{DownloadInfo, SupportedApp};
+use async_trait::async_trait;
+use log::{debug, warn};
+use moka::future::Cache;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+use url::Url;
+
+static CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+
+// Product indexes hold every build ever published, so they are fetched sparingly
+static INDEX_CACHE: LazyLock<Cache<String, Arc<ProductIndex>>> = LazyLock::new(|| {
+  Cache::builder()
+    .max_capacity(32)
+    .time_to_live(Duration::from_secs(
+      CONFIG.load().hashicorp.index_ttl_seconds,
+    ))
+    .build()
+});
+
+/// `{product}/index.json` on the releases site.
+#[derive(Debug, Deserialize)]
+struct ProductIndex {
+  versions: HashMap<String, IndexVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexVersion {
+  /// File name of the `SHA256SUMS` file next to the builds
+  #[serde(default)]
+  shasums: Option<String>,
+  #[serde(default)]
+  builds: Vec<Build>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Build {
+  os: String,
+  arch: String,
+  filename: String,
+  url: String,
+}
+
+/// Products on the HashiCorp releases site, such as terraform, vault and packer.
+pub(crate) struct HashicorpProvider;
+
+#[async_trait]
+impl ReleaseProvider for HashicorpProvider {
+  /// Prereleases (`1.6.0-rc1`) and enterprise builds (`1.15.0+ent`) are left out.
+  async fn list_versions(&self, app: &SupportedApp) -> Result<Vec<String>, AppError> {
+    let index = get_index(&app.repo.get_url()?).await?;
+    let mut versions: Vec<String> = index
+      .versions
+      .keys()
+      .filter(|version| !version.contains(['-', '+']))
+      .cloned()
+      .collect();
+    versions.sort_by(|a, b| compare_versions(b, a));
+    Ok(versions)
+  }
+
+  /// Builds are listed with their os and arch, so no names need guessing. A `file_pattern`
+  /// narrows them further, e.g. to skip `.deb`-style packaging.
+  async fn list_assets(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> Result<Vec<DownloadInfo>, AppError> {
+    let product_url = app.repo.get_url()?;
+    let index = get_index(&product_url).await?;
+    let release = index
+      .versions
+      .get(version)
+      .ok_or_else(|| AppError::NoMatchingAssets {
+        repo: app.shortname.clone(),
+        target: version.to_string(),
+      })?;
+
+    let os = app.platform_names.os_name(&target_deployment.os);
+    let builds_for = |deployment: &TargetDeployment| {
+      let arch = app.platform_names.arch_name(&deployment.arch);
+      release
+        .builds
+        .iter()
+        .filter(|build| build.os == os && build.arch == arch)
+        .collect::<Vec<_>>()
+    };
+    // without a native build, take one the target runs through emulation
+    let mut deployment = target_deployment.clone();
+    let mut builds = builds_for(&deployment);
+    for arch in target_deployment.fallback_archs() {
+      if !builds.is_empty() {
+        break;
+      }
+      deployment = TargetDeployment {
+        arch,
+        ..target_deployment.clone()
+      };
+      builds = builds_for(&deployment);
+    }
+    let fallback = (deployment != *target_deployment).then(|| deployment.arch.clone());
+
+    let checksums = match &release.shasums {
+      Some(shasums) => get_checksums(&product_url, version, shasums).await,
+      None => HashMap::new(),
+    };
+    let assets = builds
+      .into_iter()
+      .filter_map(|build| {
+        let url = Url::parse(&build.url).ok()?;
+        let mut info = DownloadInfo::from_named_url(
+          build.filename.clone(),
+          url,
+          mime::APPLICATION_OCTET_STREAM,
+          0,
+        );
+        info.sha256 = checksums.get(&build.filename).cloned();
+        info.fallback = fallback.clone();
+        Some(info)
+      })
+      .collect();
+    app.match_assets(assets, version, &deployment)
+  }
+}
+
+async fn get_index(product_url: &Url) -> Result<Arc<ProductIndex>, AppError> {
+  let key = product_url.to_string();
+  if let Some(index) = INDEX_CACHE.get(&key).await {
+    return Ok(index);
+  }
+
+  let url = product_url
+    .join("index.json")
+    .map_err(|err| AppError::InvalidInput(format!("Invalid releases URL: {}", err)))?;
+  debug!("fetching release index {}", url);
+  let response = CLIENT
+    .get(url.clone())
+    .timeout(request_timeout())
+    .send()
+    .await?;
+  if response.status() == StatusCode::NOT_FOUND {
+    return Err(AppError::NoMatchingAssets {
+      repo: product_url.to_string(),
+      target: "index.json".to_string(),
+    });
+  }
+  if !response.status().is_success() {
+    return Err(AppError::Upstream(format!(
+      "{} returned status {}",
+      url,
+      response.status()
+    )));
+  }
+  let body = response.text().await?;
+  let index: ProductIndex = serde_json::from_str(&body)
+    .map_err(|err| AppError::Upstream(format!("invalid release index {}: {}", url, err)))?;
+  let index = Arc::new(index);
+  INDEX_CACHE.insert(key, index.clone()).await;
+  Ok(index)
+}
+
+/// Digests by file name from a version's `SHA256SUMS`. Installs still work without them, so a
+/// missing or unreadable file is only logged.
+async fn get_checksums(product_url: &Url, version: &str, shasums: &str) -> HashMap<String, String> {
+  let Ok(url) = product_url.join(&format!("{}/{}", version, shasums)) else {
+    return HashMap::new();
+  };
+  let body = match CLIENT
+    .get(url.clone())
+    .timeout(request_timeout())
+    .send()
+    .await
+  {
+    Ok(response) if response.status().is_success() => response.text().await.ok(),
+    Ok(response) => {
+      warn!("{} returned status {}", url, response.status());
+      None
+    }
+    Err(err) => {
+      warn!("failed to fetch {}: {}", url, err);
+      None
+    }
+  };
+  body
+    .map(|body| parse_checksums(&body, None))
+    .unwrap_or_default()
+}
+
+/// Read on every request, so a reloaded `hashicorp.timeout_seconds` applies right away.
+fn request_timeout() -> Duration {
+  Duration::from_secs(CONFIG.load().hashicorp.timeout_seconds)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::platform::{TargetArch, TargetOs};
+  use crate::providers::test_support::serve;
+  use crate::supported_apps::{AppSource, Repo};
+  use axum::routing::get;
+  use axum::Router;
+
+  const INDEX_JSON: &str = r#"{
+    "name": "tool",
+    "versions": {
+      "1.9.0": {
+        "name": "tool", "version": "1.9.0", "shasums": "tool_1.9.0_SHA256SUMS",
+        "builds": [
+          {"name": "tool", "version": "1.9.0", "os": "linux", "arch": "amd64", "filename": "tool_1.9.0_linux_amd64.zip", "url": "https://releases.example/tool/1.9.0/tool_1.9.0_linux_amd64.zip"}
+        ]
+      },
+      "1.10.0": {
+        "name": "tool", "version": "1.10.0", "shasums": "tool_1.10.0_SHA256SUMS",
+        "builds": [
+          {"name": "tool", "version": "1.10.0", "os": "linux", "arch": "amd64", "filename": "tool_1.10.0_linux_amd64.zip", "url": "https://releases.example/tool/1.10.0/tool_1.10.0_linux_amd64.zip"},
+          {"name": "tool", "version": "1.10.0", "os": "linux", "arch": "arm64", "filename": "tool_1.10.0_linux_arm64.zip", "url": "https://releases.example/tool/1.10.0/tool_1.10.0_linux_arm64.zip"},
+          {"name": "tool", "version": "1.10.0", "os": "darwin", "arch": "arm64", "filename": "tool_1.10.0_darwin_arm64.zip", "url": "https://releases.example/tool/1.10.0/tool_1.10.0_darwin_arm64.zip"}
+        ]
+      },
+      "1.11.0-rc1": {"name": "tool", "version": "1.11.0-rc1", "builds": []},
+      "1.10.0+ent": {"name": "tool", "version": "1.10.0+ent", "builds": []}
+    }
+  }"#;
+
+  const SHA256SUMS: &str = "\
+0b1f2c9a3ec9d83b8a5ae80c3b2ef2a6a8dfb8d8bd0e6f1a4b7f5c2d9e8a7b6c  tool_1.10.0_darwin_arm64.zip
+6e3bd5c8ab1f7d2c4e9a0b3f5d7c9e1a2b4d6f8a0c2e4b6d8f0a1c3e5b7d9f1a  tool_1.10.0_linux_amd64.zip
+";
+
+  async fn stub_releases() -> String {
+    let router = Router::new()
+      .route("/tool/index.json", get(|| async { INDEX_JSON }))
+      .route(
+        "/tool/1.10.0/tool_1.10.0_SHA256SUMS",
+        get(|| async { SHA256SUMS }),
+      );
+    serve(router).await
+  }
+
+  #[tokio::test]
+  async fn resolves_latest_and_attaches_checksums() {
+    let base = stub_releases().await;
+    let app = SupportedApp::new(
+      "tool",
+      Repo::Hashicorp(format!("{}/tool/", base)),
+      AppSource::Hashicorp,
+    );
+
+    let versions = HashicorpProvider.list_versions(&app).await.unwrap();
+    assert_eq!(versions, ["1.10.0", "1.9.0"]);
+    let latest = HashicorpProvider
+      .resolve_version(&app, "latest")
+      .await
+      .unwrap();
+    assert_eq!(latest, "1.10.0");
+
+    let linux = TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64);
+    let assets = HashicorpProvider
+      .list_assets(&app, &latest, &linux)
+      .await
+      .unwrap();
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0].name, "tool_1.10.0_linux_amd64.zip");
+    assert_eq!(
+      assets[0].sha256.as_deref(),
+      Some("6e3bd5c8ab1f7d2c4e9a0b3f5d7c9e1a2b4d6f8a0c2e4b6d8f0a1c3e5b7d9f1a")
+    );
+
+    // the SHA256SUMS of 1.9.0 is missing, which leaves the digest unknown
+    let older = HashicorpProvider
+      .list_assets(&app, "1.9.0", &linux)
+      .await
+      .unwrap();
+    assert_eq!(older[0].sha256, None);
+
+    let missing = HashicorpProvider
+      .list_assets(&app, "9.9.9", &linux)
+      .await
+      .unwrap_err();
+    assert!(matches!(missing, AppError::NoMatchingAssets { .. }));
+  }
+}
@@ -1 +1,252 @@
+pub(crate) mod cargo;
+pub(crate) mod checksums;
+pub(crate) mod forgejo;
 pub(crate) mod gh;
+pub(crate) mod gitlab;
+pub(crate) mod hashicorp;
+pub(crate) mod npm;
+pub(crate) mod oci;
+pub(crate) mod pypi;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub(crate) mod url_template;
+
+use crate::config::{ScoreWeights, CONFIG};
+use crate::domain::platform::TargetDeployment;
+use crate::domain::scoring;
+use crate::error::AppError;
+use crate::http::query::{InstallQueryOptions, DEFAULT_VERSION};
+use crate::providers::checksums::{attach_checksums, checksum_files};
+use crate::providers::forgejo::ForgejoProvider;
+use crate::providers::gh::{rank_target_assets, GithubProvider};
+use crate::providers::gitlab::GitlabProvider;
+use crate::providers::hashicorp::HashicorpProvider;
+use crate::providers::npm::NpmProvider;
+use crate::providers::oci::OciProvider;
+use crate::providers::pypi::PypiProvider;
+use crate::providers::url_template::UrlProvider;
+use crate::services::{installer, templating};
+use crate::supported_apps::{AppSource, DownloadInfo, SupportedApp};
+use async_trait::async_trait;
+use log::debug;
+use std::cmp::{Ordering, Reverse};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::LazyLock;
+
+/// The providers for every built-in `AppSource`.
+pub(crate) static PROVIDERS: LazyLock<ProviderRegistry> = LazyLock::new(ProviderRegistry::default);
+
+/// Where an app's releases come from. `installer::load_app` resolves a version and lists its
+/// assets through this, without knowing which source it is talking to.
+#[async_trait]
+pub(crate) trait ReleaseProvider: Send + Sync {
+  /// Released versions of the app, newest first, without prereleases.
+  async fn list_versions(&self, app: &SupportedApp) -> Result<Vec<String>, AppError>;
+
+  /// The plain version for a requested one: `latest` becomes the newest release, and tags such
+  /// as `v1.2.0` lose their decoration.
+  async fn resolve_version(&self, app: &SupportedApp, version: &str) -> Result<String, AppError> {
+    if version != DEFAULT_VERSION {
+      return Ok(app.version_from_tag(version));
+    }
+    self
+      .list_versions(app)
+      .await?
+      .into_iter()
+      .next()
+      .ok_or_else(|| AppError::NoMatchingAssets {
+        repo: app.shortname.clone(),
+        target: version.to_string(),
+      })
+  }
+
+  /// The files of a resolved version that suit `target_deployment`. More than one means the
+  /// caller still has to choose.
+  async fn list_assets(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> Result<Vec<DownloadInfo>, AppError>;
+
+  /// The install script for the app and its file extension. Sources publishing files get the
+  /// download script for the assets `load_app` picks, package registries their own installer.
+  async fn render_script(
+    &self,
+    app: &SupportedApp,
+    query: &InstallQueryOptions,
+  ) -> Result<(String, &'static str), AppError> {
+    let loaded = installer::load_app_from(self, query, app).await?;
+    templating::render_install_script(query, &loaded, app)
+  }
+
+  /// Whether releases are built per platform. Python packages, for one, are not.
+  fn platform_specific(&self) -> bool {
+    true
+  }
+
+  /// SHA-256 digests by file name for a resolved version. Without checksum files of its own, a
+  /// source reports the digests its assets carry for each of the app's platforms.
+  async fn fetch_checksums(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+  ) -> Result<HashMap<String, String>, AppError> {
+    let mut checksums = HashMap::new();
+    let platforms = match self.platform_specific() {
+      true => app.platforms.as_slice(),
+      false => &app.platforms[..app.platforms.len().min(1)],
+    };
+    for platform in platforms {
+      let assets = match self.list_assets(app, version, platform).await {
+        Err(AppError::NoMatchingAssets { .. }) => continue,
+        assets => assets?,
+      };
+      checksums.extend(
+        assets
+          .into_iter()
+          .filter_map(|asset| Some((asset.name, asset.sha256?))),
+      );
+    }
+    Ok(checksums)
+  }
+}
+
+/// Release providers keyed by `SupportedApp.source`.
+pub(crate) struct ProviderRegistry {
+  providers: HashMap<AppSource, Box<dyn ReleaseProvider>>,
+}
+
+impl ProviderRegistry {
+  pub(crate) fn new() -> Self {
+    ProviderRegistry {
+      providers: HashMap::new(),
+    }
+  }
+
+  /// Registers `provider` for `source`, replacing any provider it had.
+  pub(crate) fn with(
+    mut self,
+    source: AppSource,
+    provider: impl ReleaseProvider + 'static,
+  ) -> Self {
+    self.providers.insert(source, Box::new(provider));
+    self
+  }
+
+  pub(crate) fn get(&self, source: AppSource) -> Result<&dyn ReleaseProvider, AppError> {
+    self
+      .providers
+      .get(&source)
+      .map(Box::as_ref)
+      .ok_or_else(|| AppError::InvalidInput(format!("no release provider for source '{}'", source)))
+  }
+}
+
+impl Default for ProviderRegistry {
+  fn default() -> Self {
+    ProviderRegistry::new()
+      .with(AppSource::Github, GithubProvider)
+      .with(AppSource::Gitlab, GitlabProvider)
+      .with(AppSource::Forgejo, ForgejoProvider)
+      .with(AppSource::Hashicorp, HashicorpProvider)
+      .with(AppSource::Url, UrlProvider)
+      .with(AppSource::Pip, PypiProvider)
+      .with(AppSource::Npm, NpmProvider)
+      .with(AppSource::Oci, OciProvider)
+  }
+}
+
+/// Fetches the release for a plain version, trying each of the app's candidate tags in turn
+/// until one exists.
+pub(crate) async fn find_release<T, F, Fut>(
+  app: &SupportedApp,
+  version: &str,
+  fetch: F,
+) -> Result<T, AppError>
+where
+  F: Fn(String) -> Fut,
+  Fut: Future<Output = Result<T, AppError>>,
+{
+  let mut missing = None;
+  for tag in app.release_tags(version) {
+    match fetch(tag).await {
+      Err(err @ AppError::NoMatchingAssets { .. }) => missing = Some(err),
+      found => return found,
+    }
+  }
+  Err(missing.unwrap_or_else(|| AppError::NoMatchingAssets {
+    repo: app.shortname.clone(),
+    target: version.to_string(),
+  }))
+}
+
+/// Narrows the assets of a release down to the target, with the app's `file_pattern` when it has
+/// one and by guessing from asset names otherwise, and ranks them with the app's weights.
+pub(crate) fn target_assets(
+  app: &SupportedApp,
+  assets: Vec<DownloadInfo>,
+  version: &str,
+  target_deployment: &TargetDeployment,
+) -> Result<Vec<DownloadInfo>, AppError> {
+  let config = CONFIG.load();
+  let weights = config.scoring.weights_for(&app.shortname);
+  match app.file_pattern {
+    Some(_) => Ok(rank_assets(
+      app.match_assets(assets, version, target_deployment)?,
+      target_deployment,
+      &weights,
+    )),
+    None => Ok(rank_target_assets(assets, target_deployment, &weights)),
+  }
+}
+
+/// Scores `assets` for the target and orders them best first, keeping their order on ties.
+pub(crate) fn rank_assets(
+  mut assets: Vec<DownloadInfo>,
+  target_deployment: &TargetDeployment,
+  weights: &ScoreWeights,
+) -> Vec<DownloadInfo> {
+  for asset in assets.iter_mut() {
+    let score = scoring::score(&asset.name, &asset.target, target_deployment, weights);
+    debug!("score={:<5} name={:?}", score, asset.name);
+    asset.score = Some(score);
+    if asset.fallback.is_none() && scoring::is_fallback(&asset.target, target_deployment) {
+      asset.fallback = Some(asset.target.deployment.arch.clone());
+    }
+  }
+  assets.sort_by_key(|asset| Reverse(asset.score));
+  assets
+}
+
+/// `target_assets`, with digests from the release's checksum files attached to what is left.
+pub(crate) async fn checksummed_target_assets(
+  app: &SupportedApp,
+  assets: Vec<DownloadInfo>,
+  version: &str,
+  target_deployment: &TargetDeployment,
+) -> Result<Vec<DownloadInfo>, AppError> {
+  let files = checksum_files(&assets);
+  let mut assets = target_assets(app, assets, version, target_deployment)?;
+  attach_checksums(&mut assets, &files).await;
+  Ok(assets)
+}
+
+/// Orders versions by their numeric release segments, e.g. `4.10.0 > 4.9.1`.
+pub(crate) fn compare_versions(a: &str, b: &str) -> Ordering {
+  let segments = |version: &str| -> Vec<u64> {
+    version
+      .split('.')
+      .map(|part| {
+        part
+          .chars()
+          .take_while(char::is_ascii_digit)
+          .collect::<String>()
+          .parse()
+          .unwrap_or(0)
+      })
+      .collect()
+  };
+  segments(a).cmp(&segments(b))
+}
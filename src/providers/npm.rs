@@ -0,0 +1,236 @@
+use crate::config::CONFIG;
+use crate::domain::platform::TargetDeployment;
+use crate::error::AppError;
+use crate::http::query::InstallQueryOptions;
+use crate::providers::{compare_versions, ReleaseProvider};
+use crate::services::templating;
+use crate::supported_apps::{DownloadInfo, Repo, SupportedApp};
+use async_trait::async_trait;
+use log::debug;
+use reqwest::header::ACCEPT;
+use reqwest::{Client, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::Duration;
+use url::Url;
+
+/// Abbreviated package metadata, with the full document as a fallback for registries without it.
+const INSTALL_METADATA_ACCEPT: &str =
+  "application/vnd.npm.install-v1+json; q=1.0, application/json; q=0.8";
+
+static CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+
+/// A resolved package version and the tarball published for it.
+#[derive(Debug)]
+pub(crate) struct NpmRelease {
+  pub(crate) package: String,
+  pub(crate) version: String,
+  pub(crate) registry_url: String,
+  pub(crate) tarball: DownloadInfo,
+  /// Executables the package links into `bin`
+  pub(crate) binaries: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Packument {
+  #[serde(rename = "dist-tags", default)]
+  dist_tags: HashMap<String, String>,
+  #[serde(default)]
+  versions: HashMap<String, PackageVersion>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageVersion {
+  dist: Dist,
+  /// Either a single path, linked under the package name, or a name-to-path map
+  #[serde(default)]
+  bin: serde_json::Value,
+  #[serde(default)]
+  deprecated: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Dist {
+  tarball: String,
+}
+
+/// Resolves `version`, a dist-tag such as `latest` or `next` or a plain version, for an `npm`
+/// app against its registry.
+pub(crate) async fn get_npm_release(repo: &Repo, version: &str) -> Result<NpmRelease, AppError> {
+  let package = repo.get_npm_package()?;
+  let package_url = repo.get_url()?;
+  let packument = fetch_packument(&package_url).await?;
+
+  let resolved =
+    resolve_version(&packument, version).ok_or_else(|| AppError::NoMatchingAssets {
+      repo: package.clone(),
+      target: version.to_string(),
+    })?;
+  debug!("resolved {} to version {}", package, resolved);
+  let published = &packument.versions[&resolved];
+
+  let url = Url::parse(&published.dist.tarball)
+    .map_err(|err| AppError::Upstream(format!("invalid tarball URL for {}: {}", package, err)))?;
+  let mut tarball = DownloadInfo::from_url(url, "application/gzip".parse().unwrap(), 0);
+  tarball.version = Some(resolved.clone());
+
+  let unscoped = package.rsplit('/').next().unwrap_or(&package).to_string();
+  let mut binaries = match &published.bin {
+    serde_json::Value::String(_) => vec![unscoped],
+    serde_json::Value::Object(named) => named.keys().cloned().collect(),
+    _ => vec![],
+  };
+  binaries.sort();
+
+  Ok(NpmRelease {
+    package,
+    version: resolved,
+    registry_url: package_url
+      .as_str()
+      .rsplit_once('/')
+      .map(|(registry, _)| registry.to_string())
+      .unwrap_or_default(),
+    tarball,
+    binaries,
+  })
+}
+
+/// Packages from an npm registry. Native code, if any, is picked by npm at install time, so one
+/// tarball serves every platform.
+pub(crate) struct NpmProvider;
+
+#[async_trait]
+impl ReleaseProvider for NpmProvider {
+  /// Prereleases and deprecated versions are left out.
+  async fn list_versions(&self, app: &SupportedApp) -> Result<Vec<String>, AppError> {
+    let packument = fetch_packument(&app.repo.get_url()?).await?;
+    let mut versions: Vec<String> = packument
+      .versions
+      .iter()
+      .filter(|(version, published)| !version.contains('-') && published.deprecated.is_none())
+      .map(|(version, _)| version.clone())
+      .collect();
+    versions.sort_by(|a, b| compare_versions(b, a));
+    Ok(versions)
+  }
+
+  /// Dist-tags are resolved by the registry rather than by picking the highest version.
+  async fn resolve_version(&self, app: &SupportedApp, version: &str) -> Result<String, AppError> {
+    Ok(get_npm_release(&app.repo, version).await?.version)
+  }
+
+  async fn list_assets(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+    _target_deployment: &TargetDeployment,
+  ) -> Result<Vec<DownloadInfo>, AppError> {
+    Ok(vec![get_npm_release(&app.repo, version).await?.tarball])
+  }
+
+  /// Packages are installed globally with npm rather than downloaded.
+  async fn render_script(
+    &self,
+    app: &SupportedApp,
+    query: &InstallQueryOptions,
+  ) -> Result<(String, &'static str), AppError> {
+    let release = get_npm_release(&app.repo, &query.version).await?;
+    templating::render_npm_install_script(query, &release)
+  }
+
+  fn platform_specific(&self) -> bool {
+    false
+  }
+}
+
+fn resolve_version(packument: &Packument, version: &str) -> Option<String> {
+  let resolved = match packument.dist_tags.get(version) {
+    Some(tagged) => tagged.as_str(),
+    None => version.trim_start_matches('v'),
+  };
+  packument
+    .versions
+    .contains_key(resolved)
+    .then(|| resolved.to_string())
+}
+
+async fn fetch_packument(package_url: &Url) -> Result<Packument, AppError> {
+  let response = CLIENT
+    .get(package_url.clone())
+    .timeout(Duration::from_secs(CONFIG.load().npm.timeout_seconds))
+    .header(ACCEPT, INSTALL_METADATA_ACCEPT)
+    .send()
+    .await?;
+  if response.status() == StatusCode::NOT_FOUND {
+    return Err(AppError::NoMatchingAssets {
+      repo: package_url.to_string(),
+      target: "package metadata".to_string(),
+    });
+  }
+  if !response.status().is_success() {
+    return Err(AppError::Upstream(format!(
+      "{} returned status {}",
+      package_url,
+      response.status()
+    )));
+  }
+  let body = response.text().await?;
+  serde_json::from_str(&body).map_err(|err| {
+    AppError::Upstream(format!(
+      "invalid package metadata from {}: {}",
+      package_url, err
+    ))
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::providers::test_support::serve;
+  use crate::supported_apps::AppSource;
+  use axum::routing::get;
+  use axum::Router;
+
+  const PACKUMENT: &str = r#"{
+    "name": "@acme/tool",
+    "dist-tags": {"latest": "2.1.0", "next": "3.0.0-beta.2"},
+    "versions": {
+      "1.4.0": {"name": "@acme/tool", "version": "1.4.0", "bin": "cli.js", "dist": {"tarball": "https://registry.example/@acme/tool/-/tool-1.4.0.tgz"}},
+      "2.1.0": {"name": "@acme/tool", "version": "2.1.0", "bin": {"tool": "cli.js", "tool-lsp": "lsp.js"}, "dist": {"tarball": "https://registry.example/@acme/tool/-/tool-2.1.0.tgz"}},
+      "2.2.0": {"name": "@acme/tool", "version": "2.2.0", "deprecated": "broken build", "dist": {"tarball": "https://registry.example/@acme/tool/-/tool-2.2.0.tgz"}},
+      "3.0.0-beta.2": {"name": "@acme/tool", "version": "3.0.0-beta.2", "dist": {"tarball": "https://registry.example/@acme/tool/-/tool-3.0.0-beta.2.tgz"}}
+    }
+  }"#;
+
+  /// A registry serving just `@acme/tool`, standing in for registry.npmjs.org or verdaccio.
+  async fn stub_registry() -> String {
+    let router = Router::new().route("/@acme%2Ftool", get(|| async { PACKUMENT }));
+    serve(router).await
+  }
+
+  #[tokio::test]
+  async fn resolves_dist_tags_and_versions() {
+    let base = stub_registry().await;
+    let repo = Repo::Npm(format!("{}/@acme%2Ftool", base));
+
+    let latest = get_npm_release(&repo, "latest").await.unwrap();
+    assert_eq!(latest.package, "@acme/tool");
+    assert_eq!(latest.registry_url, base);
+    assert_eq!(latest.version, "2.1.0");
+    assert_eq!(latest.tarball.name, "tool-2.1.0.tgz");
+    assert_eq!(latest.binaries, ["tool", "tool-lsp"]);
+
+    let next = get_npm_release(&repo, "next").await.unwrap();
+    assert_eq!(next.version, "3.0.0-beta.2");
+    let pinned = get_npm_release(&repo, "v1.4.0").await.unwrap();
+    assert_eq!(pinned.binaries, ["tool"]);
+
+    let missing = get_npm_release(&repo, "9.9.9").await.unwrap_err();
+    assert!(matches!(missing, AppError::NoMatchingAssets { .. }));
+
+    let app = SupportedApp::new("tool", repo, AppSource::Npm);
+    let versions = NpmProvider.list_versions(&app).await.unwrap();
+    assert_eq!(versions, ["2.1.0", "1.4.0"]);
+  }
+}
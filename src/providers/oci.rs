@@ -0,0 +1,594 @@
+use crate::config::CONFIG;
+use crate::domain::platform::TargetDeployment;
+use crate::error::AppError;
+use crate::http::query::DEFAULT_VERSION;
+use crate::providers::{compare_versions, find_release, target_assets, ReleaseProvider};
+use crate::supported_apps::{DownloadInfo, Repo, SupportedApp};
+use async_trait::async_trait;
+use flate2::read::GzDecoder;
+use log::debug;
+use mime::Mime;
+use regex::Regex;
+use reqwest::header::{ACCEPT, LOCATION, WWW_AUTHENTICATE};
+use reqwest::{redirect, Client, Response, StatusCode};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::LazyLock;
+use std::time::Duration;
+use url::Url;
+
+const MANIFEST_ACCEPT: &str = "application/vnd.oci.image.index.v1+json, \
+  application/vnd.docker.distribution.manifest.list.v2+json, \
+  application/vnd.oci.image.manifest.v1+json, \
+  application/vnd.docker.distribution.manifest.v2+json";
+/// Annotation ORAS stores each pushed file's name under
+const TITLE_ANNOTATION: &str = "org.opencontainers.image.title";
+
+static CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+
+// Blob requests are answered with a redirect to storage the install script can reach without a token
+static NO_REDIRECT_CLIENT: LazyLock<Client> = LazyLock::new(|| {
+  Client::builder()
+    .redirect(redirect::Policy::none())
+    .build()
+    .expect("Failed to build HTTP client")
+});
+
+static CHALLENGE_PARAM: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r#"(\w+)="([^"]*)""#).unwrap());
+
+#[derive(Debug, Deserialize)]
+struct TagList {
+  #[serde(default)]
+  tags: Option<Vec<String>>,
+}
+
+/// An image index or an image manifest; which one depends on the fields present.
+#[derive(Debug, Deserialize)]
+struct Manifest {
+  #[serde(default)]
+  manifests: Vec<Descriptor>,
+  #[serde(default)]
+  layers: Vec<Descriptor>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Descriptor {
+  #[serde(rename = "mediaType", default)]
+  media_type: String,
+  digest: String,
+  #[serde(default)]
+  size: u64,
+  #[serde(default)]
+  platform: Option<Platform>,
+  #[serde(default)]
+  annotations: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Platform {
+  os: String,
+  architecture: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+  #[serde(alias = "access_token")]
+  token: String,
+}
+
+/// CLIs published as ORAS artifacts or inside container images, on any registry speaking the
+/// OCI distribution API.
+pub(crate) struct OciProvider;
+
+#[async_trait]
+impl ReleaseProvider for OciProvider {
+  /// Tags that look like releases, so neither `latest`, prereleases nor signature tags.
+  async fn list_versions(&self, app: &SupportedApp) -> Result<Vec<String>, AppError> {
+    let tags = Session::default().list_tags(&app.repo).await?;
+    Ok(release_versions(app, &tags))
+  }
+
+  /// `latest` is the newest release tag, or the `latest` tag of repos that only push that.
+  async fn resolve_version(&self, app: &SupportedApp, version: &str) -> Result<String, AppError> {
+    if version != DEFAULT_VERSION {
+      return Ok(app.version_from_tag(version));
+    }
+    let tags = Session::default().list_tags(&app.repo).await?;
+    match release_versions(app, &tags).into_iter().next() {
+      Some(version) => Ok(version),
+      None if tags.iter().any(|tag| tag == DEFAULT_VERSION) => Ok(DEFAULT_VERSION.to_string()),
+      None => Err(AppError::NoMatchingAssets {
+        repo: app.shortname.clone(),
+        target: version.to_string(),
+      }),
+    }
+  }
+
+  /// Picks the target's manifest out of an image index, then offers its layers. ORAS files keep
+  /// their pushed names; an image layer is chosen by the app's first `archive_path` when the
+  /// image has several.
+  async fn list_assets(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> Result<Vec<DownloadInfo>, AppError> {
+    let (tag, manifest, mut session) = find_release(app, version, |tag| async move {
+      let mut session = Session::default();
+      let manifest = session.get_manifest(&app.repo, &tag).await?;
+      Ok((tag, manifest, session))
+    })
+    .await?;
+
+    let (manifest, platform_selected) = if manifest.manifests.is_empty() {
+      (manifest, false)
+    } else {
+      let os = target_deployment.os.release_name();
+      let arch = target_deployment.arch.release_name();
+      let selected = manifest
+        .manifests
+        .iter()
+        .find(|descriptor| {
+          descriptor
+            .platform
+            .as_ref()
+            .is_some_and(|platform| platform.os == os && platform.architecture == arch)
+        })
+        .ok_or_else(|| AppError::NoMatchingAssets {
+          repo: app.shortname.clone(),
+          target: format!("{}/{}", os, arch),
+        })?;
+      debug!(
+        "{}:{} resolved to {} for {}/{}",
+        app.shortname, tag, selected.digest, os, arch
+      );
+      (
+        session.get_manifest(&app.repo, &selected.digest).await?,
+        true,
+      )
+    };
+
+    let version = app.version_from_tag(&tag);
+    if manifest
+      .layers
+      .iter()
+      .any(|layer| layer.annotations.contains_key(TITLE_ANNOTATION))
+    {
+      let mut files = vec![];
+      for layer in manifest
+        .layers
+        .iter()
+        .filter(|layer| layer.annotations.contains_key(TITLE_ANNOTATION))
+      {
+        let name = layer.annotations[TITLE_ANNOTATION].clone();
+        files.push(session.download_info(&app.repo, layer, name).await?);
+      }
+      return match platform_selected && app.file_pattern.is_none() {
+        true => Ok(files),
+        false => target_assets(app, files, &version, target_deployment),
+      };
+    }
+
+    let layers = match app.binaries.first() {
+      Some(binary) if manifest.layers.len() > 1 => session
+        .layer_with_file(&app.repo, &manifest.layers, &binary.archive_path)
+        .await?
+        .into_iter()
+        .collect(),
+      _ => manifest.layers,
+    };
+    let mut assets = vec![];
+    for layer in &layers {
+      let name = format!(
+        "{}-{}-{}-{}{}",
+        app.shortname,
+        version,
+        target_deployment.os.release_name(),
+        target_deployment.arch.release_name(),
+        layer_extension(&layer.media_type)
+      );
+      assets.push(session.download_info(&app.repo, layer, name).await?);
+    }
+    Ok(assets)
+  }
+}
+
+/// Requests against one repository, carrying the pull token once the registry asked for one.
+#[derive(Debug, Default)]
+struct Session {
+  token: Option<String>,
+}
+
+impl Session {
+  async fn list_tags(&mut self, repo: &Repo) -> Result<Vec<String>, AppError> {
+    let url = repo_url(repo, "tags/list?n=1000")?;
+    let response = self.get(&url, "application/json").await?;
+    let tags: TagList = parse_json(&url, response).await?;
+    Ok(tags.tags.unwrap_or_default())
+  }
+
+  async fn get_manifest(&mut self, repo: &Repo, reference: &str) -> Result<Manifest, AppError> {
+    let url = repo_url(repo, &format!("manifests/{}", reference))?;
+    let response = self.get(&url, MANIFEST_ACCEPT).await?;
+    parse_json(&url, response).await
+  }
+
+  /// Sends a GET, fetching an anonymous pull token first if the registry challenges for one.
+  async fn get(&mut self, url: &Url, accept: &str) -> Result<Response, AppError> {
+    let mut response = self.send(url, accept).await?;
+    if response.status() == StatusCode::UNAUTHORIZED && self.token.is_none() {
+      let challenge = response
+        .headers()
+        .get(WWW_AUTHENTICATE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+      self.token = Some(fetch_token(&challenge).await?);
+      response = self.send(url, accept).await?;
+    }
+    if response.status() == StatusCode::NOT_FOUND {
+      debug!("{} returned status {}", url, response.status());
+      return Err(AppError::NoMatchingAssets {
+        repo: url.to_string(),
+        target: url
+          .path()
+          .rsplit('/')
+          .next()
+          .unwrap_or_default()
+          .to_string(),
+      });
+    }
+    if !response.status().is_success() {
+      return Err(AppError::Upstream(format!(
+        "{} returned status {}",
+        url,
+        response.status()
+      )));
+    }
+    Ok(response)
+  }
+
+  async fn send(&self, url: &Url, accept: &str) -> Result<Response, AppError> {
+    let mut request = CLIENT
+      .get(url.clone())
+      .timeout(request_timeout())
+      .header(ACCEPT, accept);
+    if let Some(token) = &self.token {
+      request = request.bearer_auth(token);
+    }
+    Ok(request.send().await?)
+  }
+
+  /// Describes a blob by a URL the install script can fetch. Registries that wanted a token
+  /// redirect blob requests to storage, which is handed out instead.
+  async fn download_info(
+    &self,
+    repo: &Repo,
+    layer: &Descriptor,
+    name: String,
+  ) -> Result<DownloadInfo, AppError> {
+    let blob_url = repo_url(repo, &format!("blobs/{}", layer.digest))?;
+    let url = match &self.token {
+      None => blob_url,
+      Some(token) => {
+        let response = NO_REDIRECT_CLIENT
+          .get(blob_url.clone())
+          .timeout(request_timeout())
+          .bearer_auth(token)
+          .send()
+          .await?;
+        let location = response
+          .headers()
+          .get(LOCATION)
+          .and_then(|value| value.to_str().ok())
+          .filter(|_| response.status().is_redirection())
+          .ok_or_else(|| {
+            AppError::Upstream(format!(
+              "{} only serves blobs with a token, which install scripts cannot pass on",
+              blob_url
+            ))
+          })?;
+        blob_url
+          .join(location)
+          .map_err(|err| AppError::Upstream(format!("invalid blob redirect: {}", err)))?
+      }
+    };
+    let mut info =
+      DownloadInfo::from_named_url(name, url, layer_mime(&layer.media_type), layer.size);
+    info.sha256 = layer.digest.strip_prefix("sha256:").map(str::to_string);
+    Ok(info)
+  }
+
+  /// The topmost layer containing `path`. Layers are downloaded and listed one by one, skipping
+  /// any above `oci.max_layer_bytes`.
+  async fn layer_with_file(
+    &mut self,
+    repo: &Repo,
+    layers: &[Descriptor],
+    path: &str,
+  ) -> Result<Option<Descriptor>, AppError> {
+    let max_layer_bytes = CONFIG.load().oci.max_layer_bytes;
+    for layer in layers.iter().rev() {
+      if layer.size > max_layer_bytes {
+        debug!("skipping layer {} of {} bytes", layer.digest, layer.size);
+        continue;
+      }
+      let url = repo_url(repo, &format!("blobs/{}", layer.digest))?;
+      let blob = self.get(&url, "*/*").await?.bytes().await?;
+      let media_type = layer.media_type.clone();
+      let wanted = path.to_string();
+      let found = tokio::task::spawn_blocking(move || layer_contains(&blob, &media_type, &wanted))
+        .await
+        .map_err(|err| AppError::Upstream(format!("failed to read layer: {}", err)))?;
+      if found {
+        return Ok(Some(layer.clone()));
+      }
+    }
+    Ok(None)
+  }
+}
+
+/// Trades a `WWW-Authenticate: Bearer realm=...,service=...,scope=...` challenge for a token.
+async fn fetch_token(challenge: &str) -> Result<String, AppError> {
+  let params: HashMap<&str, &str> = CHALLENGE_PARAM
+    .captures_iter(challenge)
+    .filter_map(|captures| Some((captures.get(1)?.as_str(), captures.get(2)?.as_str())))
+    .collect();
+  let realm = params
+    .get("realm")
+    .ok_or_else(|| AppError::Upstream(format!("unsupported registry challenge: {}", challenge)))?;
+  let mut url = Url::parse(realm)
+    .map_err(|err| AppError::Upstream(format!("invalid token realm {}: {}", realm, err)))?;
+  for key in ["service", "scope"] {
+    if let Some(value) = params.get(key) {
+      url.query_pairs_mut().append_pair(key, value);
+    }
+  }
+  debug!("fetching registry token from {}", url);
+  let response = CLIENT
+    .get(url.clone())
+    .timeout(request_timeout())
+    .send()
+    .await?;
+  if !response.status().is_success() {
+    return Err(AppError::Upstream(format!(
+      "{} returned status {}",
+      url,
+      response.status()
+    )));
+  }
+  let token: TokenResponse = parse_json(&url, response).await?;
+  Ok(token.token)
+}
+
+async fn parse_json<T: for<'de> Deserialize<'de>>(
+  url: &Url,
+  response: Response,
+) -> Result<T, AppError> {
+  let body = response.text().await?;
+  serde_json::from_str(&body)
+    .map_err(|err| AppError::Upstream(format!("invalid registry response from {}: {}", url, err)))
+}
+
+fn repo_url(repo: &Repo, path: &str) -> Result<Url, AppError> {
+  Url::parse(&format!("{}{}", repo.get_url()?, path))
+    .map_err(|err| AppError::InvalidInput(format!("Invalid registry URL: {}", err)))
+}
+
+fn release_versions(app: &SupportedApp, tags: &[String]) -> Vec<String> {
+  let mut versions: Vec<String> = tags
+    .iter()
+    .map(|tag| app.version_from_tag(tag))
+    .filter(|version| {
+      version.starts_with(|c: char| c.is_ascii_digit()) && !version.contains(['-', '+'])
+    })
+    .collect();
+  versions.sort_by(|a, b| compare_versions(b, a));
+  versions.dedup();
+  versions
+}
+
+fn layer_extension(media_type: &str) -> &'static str {
+  if media_type.ends_with("gzip") {
+    ".tar.gz"
+  } else if media_type.ends_with("zstd") {
+    ".tar.zst"
+  } else {
+    ".tar"
+  }
+}
+
+fn layer_mime(media_type: &str) -> Mime {
+  if media_type.ends_with("gzip") {
+    "application/gzip".parse().unwrap()
+  } else if media_type.ends_with("tar") {
+    "application/x-tar".parse().unwrap()
+  } else {
+    mime::APPLICATION_OCTET_STREAM
+  }
+}
+
+/// Whether a tar layer, gzipped or not, has an entry at `path`.
+fn layer_contains(blob: &[u8], media_type: &str, path: &str) -> bool {
+  let reader: Box<dyn Read> = if media_type.ends_with("gzip") {
+    Box::new(GzDecoder::new(blob))
+  } else if media_type.ends_with("tar") {
+    Box::new(blob)
+  } else {
+    return false;
+  };
+  let wanted = path.trim_start_matches("./").trim_start_matches('/');
+  let mut archive = tar::Archive::new(reader);
+  let Ok(entries) = archive.entries() else {
+    return false;
+  };
+  entries.flatten().any(|entry| {
+    entry.path().is_ok_and(|entry_path| {
+      let entry_path = entry_path.to_string_lossy();
+      entry_path.trim_start_matches("./").trim_start_matches('/') == wanted
+    })
+  })
+}
+
+/// Registry requests time out after `oci.timeout_seconds`, as currently configured.
+fn request_timeout() -> Duration {
+  Duration::from_secs(CONFIG.load().oci.timeout_seconds)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::platform::{TargetArch, TargetOs};
+  use crate::providers::test_support::serve_with;
+  use crate::supported_apps::{parse_registry, AppSource};
+  use axum::extract::{Path, State};
+  use axum::http::{HeaderMap, StatusCode as HttpStatus};
+  use axum::response::{IntoResponse, Redirect};
+  use axum::routing::get;
+  use axum::Router;
+  use flate2::write::GzEncoder;
+  use flate2::Compression;
+
+  const TOKEN: &str = "pull-token";
+
+  fn layer(files: &[&str]) -> Vec<u8> {
+    let mut builder = tar::Builder::new(GzEncoder::new(vec![], Compression::fast()));
+    for file in files {
+      let mut header = tar::Header::new_gnu();
+      header.set_size(4);
+      header.set_mode(0o755);
+      header.set_cksum();
+      builder
+        .append_data(&mut header, file, &b"\x7fELF"[..])
+        .unwrap();
+    }
+    builder.into_inner().unwrap().finish().unwrap()
+  }
+
+  /// A registry serving `tool:v1.2.0` as a two-platform image and `tool-files:1.0.0` as an ORAS
+  /// artifact. Every request wants the pull token, and blobs redirect to a download path.
+  async fn stub_registry() -> String {
+    async fn token() -> impl IntoResponse {
+      format!("{{\"token\":\"{}\"}}", TOKEN)
+    }
+
+    async fn registry(
+      State(base): State<String>,
+      Path((repo, kind, reference)): Path<(String, String, String)>,
+      headers: HeaderMap,
+    ) -> axum::response::Response {
+      let authorized = headers
+        .get("authorization")
+        .is_some_and(|value| value == format!("Bearer {}", TOKEN).as_str());
+      if !authorized {
+        let challenge = format!(
+          "Bearer realm=\"{}/token\",service=\"stub\",scope=\"repository:{}:pull\"",
+          base, repo
+        );
+        return (HttpStatus::UNAUTHORIZED, [("WWW-Authenticate", challenge)]).into_response();
+      }
+      let body = match (repo.as_str(), kind.as_str(), reference.as_str()) {
+        ("tool", "tags", "list") => r#"{"name":"tool","tags":["v1.1.0","v1.2.0","v1.3.0-rc1","latest","sha256-aa.sig"]}"#.to_string(),
+        ("tool", "manifests", "v1.2.0") => r#"{"schemaVersion":2,"mediaType":"application/vnd.oci.image.index.v1+json","manifests":[
+          {"mediaType":"application/vnd.oci.image.manifest.v1+json","digest":"sha256:amd64","size":1,"platform":{"os":"linux","architecture":"amd64"}},
+          {"mediaType":"application/vnd.oci.image.manifest.v1+json","digest":"sha256:arm64","size":1,"platform":{"os":"linux","architecture":"arm64"}}
+        ]}"#.to_string(),
+        ("tool", "manifests", "sha256:amd64") => r#"{"schemaVersion":2,"mediaType":"application/vnd.oci.image.manifest.v1+json","layers":[
+          {"mediaType":"application/vnd.oci.image.layer.v1.tar+gzip","digest":"sha256:base","size":10},
+          {"mediaType":"application/vnd.oci.image.layer.v1.tar+gzip","digest":"sha256:bin","size":10},
+          {"mediaType":"application/vnd.oci.image.layer.v1.tar+gzip","digest":"sha256:config","size":10}
+        ]}"#.to_string(),
+        ("tool-files", "manifests", "1.0.0") => r#"{"schemaVersion":2,"mediaType":"application/vnd.oci.image.manifest.v1+json","artifactType":"application/vnd.acme.tool","layers":[
+          {"mediaType":"application/octet-stream","digest":"sha256:0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef","size":4,"annotations":{"org.opencontainers.image.title":"tool-linux-amd64"}},
+          {"mediaType":"application/octet-stream","digest":"sha256:fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210","size":4,"annotations":{"org.opencontainers.image.title":"tool-darwin-arm64"}}
+        ]}"#.to_string(),
+        (_, "blobs", digest) => {
+          return Redirect::temporary(&format!("{}/download/{}", base, digest)).into_response()
+        }
+        _ => return HttpStatus::NOT_FOUND.into_response(),
+      };
+      body.into_response()
+    }
+
+    async fn download(Path(digest): Path<String>) -> Vec<u8> {
+      match digest.as_str() {
+        "sha256:base" => layer(&["etc/os-release"]),
+        "sha256:bin" => layer(&["./usr/local/bin/tool"]),
+        _ => layer(&["etc/tool/config.yaml"]),
+      }
+    }
+
+    serve_with(|base| {
+      Router::new()
+        .route("/token", get(token))
+        .route("/download/{digest}", get(download))
+        .route("/v2/{repo}/{kind}/{reference}", get(registry))
+        .with_state(base.to_string())
+    })
+    .await
+  }
+
+  const HEADER: &str =
+    "shortname,repo,source,file_pattern,archive_path,archive_depth,custom_release_tag\n";
+
+  fn app(row: &str) -> SupportedApp {
+    let apps = parse_registry(format!("{}{}\n", HEADER, row).as_bytes(), "test.csv").unwrap();
+    apps.into_values().next().unwrap()
+  }
+
+  #[tokio::test]
+  async fn picks_the_platform_and_the_layer_holding_the_binary() {
+    let base = stub_registry().await;
+    let host = base.trim_start_matches("http://");
+    let tool = app(&format!("tool,{}/tool,oci,-,usr/local/bin/tool,3,-", host));
+    assert_eq!(tool.source, AppSource::Oci);
+
+    assert_eq!(
+      OciProvider.list_versions(&tool).await.unwrap(),
+      ["1.2.0", "1.1.0"]
+    );
+    let latest = OciProvider.resolve_version(&tool, "latest").await.unwrap();
+    assert_eq!(latest, "1.2.0");
+
+    let linux = TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64);
+    let assets = OciProvider
+      .list_assets(&tool, &latest, &linux)
+      .await
+      .unwrap();
+    assert_eq!(assets.len(), 1);
+    assert_eq!(assets[0].name, "tool-1.2.0-linux-amd64.tar.gz");
+    assert_eq!(
+      assets[0].url.as_str(),
+      format!("{}/download/sha256:bin", base)
+    );
+    assert_eq!(assets[0].sha256.as_deref(), Some("bin"));
+
+    let windows = TargetDeployment::new(TargetOs::Windows, TargetArch::Amd64);
+    let missing = OciProvider
+      .list_assets(&tool, &latest, &windows)
+      .await
+      .unwrap_err();
+    assert!(matches!(missing, AppError::NoMatchingAssets { .. }));
+  }
+
+  #[tokio::test]
+  async fn oras_artifacts_keep_their_file_names() {
+    let base = stub_registry().await;
+    let host = base.trim_start_matches("http://");
+    let files = app(&format!("tool,{}/tool-files,oci,tool-OS-ARCH,-,-,-", host));
+
+    let mac = TargetDeployment::new(TargetOs::Mac, TargetArch::Arm64);
+    let assets = OciProvider
+      .list_assets(&files, "1.0.0", &mac)
+      .await
+      .unwrap();
+    let names: Vec<&str> = assets.iter().map(|asset| asset.name.as_str()).collect();
+    assert_eq!(names, ["tool-darwin-arm64"]);
+    assert_eq!(
+      assets[0].sha256.as_deref(),
+      Some("fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210")
+    );
+  }
+}
@@ -0,0 +1,358 @@
+use crate::config::CONFIG;
+use crate::domain::platform::TargetDeployment;
+use crate::error::AppError;
+use crate::http::query::InstallQueryOptions;
+use crate::providers::{compare_versions, ReleaseProvider};
+use crate::services::templating;
+use crate::supported_apps::{DownloadInfo, Repo, SupportedApp};
+use async_trait::async_trait;
+use log::debug;
+use mime::Mime;
+use reqwest::header::{ACCEPT, CONTENT_TYPE};
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::LazyLock;
+use std::time::Duration;
+use url::Url;
+
+const SIMPLE_JSON_MIME: &str = "application/vnd.pypi.simple.v1+json";
+const SDIST_EXTENSIONS: [&str; 3] = [".tar.gz", ".zip", ".tar.bz2"];
+
+static CLIENT: LazyLock<Client> = LazyLock::new(Client::new);
+
+/// A resolved package version and the distribution files published for it.
+#[derive(Debug)]
+pub(crate) struct PythonRelease {
+  pub(crate) package: String,
+  pub(crate) version: String,
+  pub(crate) index_url: String,
+  pub(crate) files: Vec<DownloadInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimpleProject {
+  files: Vec<SimpleFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimpleFile {
+  filename: String,
+  url: String,
+  #[serde(default)]
+  size: Option<u64>,
+  #[serde(default)]
+  yanked: serde_json::Value,
+}
+
+/// A file listed on a project page of the simple index.
+#[derive(Debug)]
+struct IndexFile {
+  filename: String,
+  url: Url,
+  size: u64,
+}
+
+/// Resolves `version` (or `latest`) for a `pip` app against its simple index.
+pub(crate) async fn get_python_release(
+  repo: &Repo,
+  version: &str,
+) -> Result<PythonRelease, AppError> {
+  let project_url = repo.get_url()?;
+  let package = repo.get_python_package()?;
+  let files = fetch_index_files(&project_url).await?;
+
+  let version = if version == "latest" {
+    stable_versions(&files)
+      .into_iter()
+      .next()
+      .ok_or_else(|| AppError::NoMatchingAssets {
+        repo: package.clone(),
+        target: "latest".to_string(),
+      })?
+  } else {
+    version.trim_start_matches('v').to_string()
+  };
+  debug!("resolved {} to version {}", package, version);
+
+  let files: Vec<DownloadInfo> = files
+    .into_iter()
+    .filter(|file| version_from_filename(&file.filename).as_deref() == Some(version.as_str()))
+    .map(|file| DownloadInfo::from_url(file.url, distribution_mime(&file.filename), file.size))
+    .collect();
+  if files.is_empty() {
+    return Err(AppError::NoMatchingAssets {
+      repo: package,
+      target: version,
+    });
+  }
+
+  let mut index_url = project_url.clone();
+  index_url
+    .path_segments_mut()
+    .map_err(|_| AppError::InvalidInput(format!("Invalid index URL: {}", project_url)))?
+    .pop_if_empty()
+    .pop();
+
+  Ok(PythonRelease {
+    package,
+    version,
+    index_url: index_url.to_string(),
+    files,
+  })
+}
+
+/// Distributions from a Python package index. The same files serve every platform.
+pub(crate) struct PypiProvider;
+
+#[async_trait]
+impl ReleaseProvider for PypiProvider {
+  async fn list_versions(&self, app: &SupportedApp) -> Result<Vec<String>, AppError> {
+    let files = fetch_index_files(&app.repo.get_url()?).await?;
+    Ok(stable_versions(&files))
+  }
+
+  async fn list_assets(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+    _target_deployment: &TargetDeployment,
+  ) -> Result<Vec<DownloadInfo>, AppError> {
+    Ok(get_python_release(&app.repo, version).await?.files)
+  }
+
+  /// Packages are installed with uv, pipx or pip rather than downloaded.
+  async fn render_script(
+    &self,
+    app: &SupportedApp,
+    query: &InstallQueryOptions,
+  ) -> Result<(String, &'static str), AppError> {
+    let release = get_python_release(&app.repo, &query.version).await?;
+    templating::render_python_install_script(query, &release, &query.os)
+  }
+
+  fn platform_specific(&self) -> bool {
+    false
+  }
+}
+
+/// Versions with at least one file, newest first and without prereleases.
+fn stable_versions(files: &[IndexFile]) -> Vec<String> {
+  let mut versions: Vec<String> = files
+    .iter()
+    .filter_map(|file| version_from_filename(&file.filename))
+    .filter(|version| !is_prerelease(version))
+    .collect();
+  versions.sort_by(|a, b| compare_versions(b, a));
+  versions.dedup();
+  versions
+}
+
+async fn fetch_index_files(project_url: &Url) -> Result<Vec<IndexFile>, AppError> {
+  let response = CLIENT
+    .get(project_url.clone())
+    .timeout(Duration::from_secs(CONFIG.load().python.timeout_seconds))
+    .header(ACCEPT, format!("{}, text/html;q=0.1", SIMPLE_JSON_MIME))
+    .send()
+    .await?;
+  if !response.status().is_success() {
+    return Err(AppError::Upstream(format!(
+      "{} returned status {}",
+      project_url,
+      response.status()
+    )));
+  }
+  let is_json = response
+    .headers()
+    .get(CONTENT_TYPE)
+    .and_then(|value| value.to_str().ok())
+    .map(|value| value.contains("json"))
+    .unwrap_or(false);
+  let body = response.text().await?;
+
+  if is_json {
+    let project: SimpleProject = serde_json::from_str(&body).map_err(|err| {
+      AppError::Upstream(format!(
+        "invalid index response from {}: {}",
+        project_url, err
+      ))
+    })?;
+    Ok(
+      project
+        .files
+        .into_iter()
+        .filter(|file| file.yanked == serde_json::Value::Bool(false) || file.yanked.is_null())
+        .filter_map(|file| {
+          let url = project_url.join(&file.url).ok()?;
+          Some(IndexFile {
+            filename: file.filename,
+            url: without_fragment(url),
+            size: file.size.unwrap_or(0),
+          })
+        })
+        .collect(),
+    )
+  } else {
+    Ok(parse_simple_html(project_url, &body))
+  }
+}
+
+/// Reads the anchors of a PEP 503 HTML project page, skipping yanked files.
+fn parse_simple_html(project_url: &Url, body: &str) -> Vec<IndexFile> {
+  body
+    .split("<a ")
+    .skip(1)
+    .filter_map(|anchor| {
+      let (attributes, rest) = anchor.split_once('>')?;
+      if attributes.contains("data-yanked") {
+        return None;
+      }
+      let href = attributes.split("href=\"").nth(1)?.split('"').next()?;
+      let filename = rest.split("</a>").next()?.trim().to_string();
+      let url = project_url.join(&href.replace("&amp;", "&")).ok()?;
+      Some(IndexFile {
+        filename,
+        url: without_fragment(url),
+        size: 0,
+      })
+    })
+    .collect()
+}
+
+fn without_fragment(mut url: Url) -> Url {
+  url.set_fragment(None);
+  url
+}
+
+fn distribution_mime(filename: &str) -> Mime {
+  if filename.ends_with(".tar.gz") {
+    "application/gzip".parse().unwrap()
+  } else if filename.ends_with(".zip") || filename.ends_with(".whl") {
+    "application/zip".parse().unwrap()
+  } else {
+    mime::APPLICATION_OCTET_STREAM
+  }
+}
+
+/// Extracts the version from a wheel (`name-1.0-py3-none-any.whl`) or sdist (`name-1.0.tar.gz`).
+fn version_from_filename(filename: &str) -> Option<String> {
+  if let Some(stem) = filename.strip_suffix(".whl") {
+    return stem.split('-').nth(1).map(str::to_string);
+  }
+  let stem = SDIST_EXTENSIONS
+    .iter()
+    .find_map(|ext| filename.strip_suffix(ext))?;
+  stem
+    .rsplit_once('-')
+    .map(|(_, version)| version.to_string())
+}
+
+/// PEP 440 pre-release (`a`, `b`, `rc` and their long spellings) and development markers. Post
+/// releases such as `1.2.post1` and local versions such as `1.2+cpu` are regular releases.
+const PRERELEASE_MARKERS: [&str; 9] = [
+  "a", "alpha", "b", "beta", "c", "rc", "pre", "preview", "dev",
+];
+
+fn is_prerelease(version: &str) -> bool {
+  let public = version
+    .split('+')
+    .next()
+    .unwrap_or(version)
+    .to_ascii_lowercase();
+  public
+    .split(|c: char| !c.is_ascii_alphabetic())
+    .any(|marker| PRERELEASE_MARKERS.contains(&marker))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::providers::test_support::serve;
+  use axum::routing::get;
+  use axum::Router;
+  use std::cmp::Ordering;
+
+  const PROJECT_JSON: &str = r#"{
+    "meta": {"api-version": "1.1"},
+    "name": "glances",
+    "files": [
+      {"filename": "glances-4.0.8.tar.gz", "url": "https://files.example/glances-4.0.8.tar.gz", "hashes": {}, "size": 10},
+      {"filename": "glances-4.1.0-py3-none-any.whl", "url": "https://files.example/glances-4.1.0-py3-none-any.whl", "hashes": {}, "size": 20},
+      {"filename": "glances-4.1.0.tar.gz", "url": "https://files.example/glances-4.1.0.tar.gz#sha256=abc", "hashes": {}, "size": 30},
+      {"filename": "glances-4.2.0rc1.tar.gz", "url": "https://files.example/glances-4.2.0rc1.tar.gz", "hashes": {}},
+      {"filename": "glances-4.3.0.tar.gz", "url": "https://files.example/glances-4.3.0.tar.gz", "hashes": {}, "yanked": "broken"}
+    ]
+  }"#;
+
+  const PROJECT_HTML: &str = r#"<!DOCTYPE html><html><body>
+    <a href="../../packages/glances-4.0.8.tar.gz#sha256=abc">glances-4.0.8.tar.gz</a><br/>
+    <a href="../../packages/glances-4.1.0.tar.gz">glances-4.1.0.tar.gz</a><br/>
+    <a href="../../packages/glances-4.3.0.tar.gz" data-yanked="">glances-4.3.0.tar.gz</a><br/>
+  </body></html>"#;
+
+  async fn stub_index() -> String {
+    let router = Router::new()
+      .route(
+        "/json/glances/",
+        get(|| async { ([("Content-Type", SIMPLE_JSON_MIME)], PROJECT_JSON) }),
+      )
+      .route(
+        "/html/glances/",
+        get(|| async { ([("Content-Type", "text/html")], PROJECT_HTML) }),
+      );
+    serve(router).await
+  }
+
+  #[test]
+  fn versions_from_distribution_filenames() {
+    assert_eq!(
+      version_from_filename("glances-4.1.0-py3-none-any.whl").as_deref(),
+      Some("4.1.0")
+    );
+    assert_eq!(
+      version_from_filename("python-dateutil-2.9.0.tar.gz").as_deref(),
+      Some("2.9.0")
+    );
+    assert_eq!(version_from_filename("glances.exe"), None);
+    assert_eq!(compare_versions("4.10.0", "4.9.1"), Ordering::Greater);
+    assert!(is_prerelease("4.2.0rc1"));
+    assert!(is_prerelease("4.2.0.dev3"));
+    assert!(is_prerelease("4.2.0-beta.2"));
+    assert!(!is_prerelease("4.2.post1"));
+    assert!(!is_prerelease("4.2.0+cpu.rc"));
+    assert!(!is_prerelease("4.2.0"));
+  }
+
+  #[tokio::test]
+  async fn resolves_latest_from_json_index() {
+    let base = stub_index().await;
+    let repo = Repo::Python(format!("{}/json/glances/", base));
+    let release = get_python_release(&repo, "latest").await.unwrap();
+    assert_eq!(release.package, "glances");
+    assert_eq!(release.version, "4.1.0");
+    assert_eq!(release.index_url, format!("{}/json", base));
+    let names: Vec<&str> = release.files.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(
+      names,
+      ["glances-4.1.0-py3-none-any.whl", "glances-4.1.0.tar.gz"]
+    );
+    assert_eq!(release.files[1].url.fragment(), None);
+  }
+
+  #[tokio::test]
+  async fn resolves_pinned_version_from_html_index() {
+    let base = stub_index().await;
+    let repo = Repo::Python(format!("{}/html/glances/", base));
+    let release = get_python_release(&repo, "v4.0.8").await.unwrap();
+    assert_eq!(release.version, "4.0.8");
+    assert_eq!(
+      release.files[0].url.as_str(),
+      format!("{}/packages/glances-4.0.8.tar.gz", base)
+    );
+
+    let latest = get_python_release(&repo, "latest").await.unwrap();
+    assert_eq!(latest.version, "4.1.0");
+
+    let missing = get_python_release(&repo, "9.9.9").await.unwrap_err();
+    assert!(matches!(missing, AppError::NoMatchingAssets { .. }));
+  }
+}
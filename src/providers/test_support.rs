@@ -0,0 +1,16 @@
+use axum::Router;
+use tokio::net::TcpListener;
+
+/// Serves `router` on a free local port, returning its `http://127.0.0.1:<port>` base URL.
+pub(crate) async fn serve(router: Router) -> String {
+  serve_with(|_| router).await
+}
+
+/// Like [`serve`], for stubs whose responses link back to the stub itself.
+pub(crate) async fn serve_with(router: impl FnOnce(&str) -> Router) -> String {
+  let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+  let base = format!("http://127.0.0.1:{}", listener.local_addr().unwrap().port());
+  let router = router(&base);
+  tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+  base
+}
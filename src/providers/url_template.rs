@@ -0,0 +1,228 @@
+use crate::domain::platform::TargetDeployment;
+use crate::error::AppError;
+use crate::providers::ReleaseProvider;
+use crate::supported_apps::{DownloadInfo, SupportedApp};
+use async_trait::async_trait;
+use log::debug;
+use mime::Mime;
+use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE};
+use reqwest::Client;
+use std::sync::LazyLock;
+use std::time::Duration;
+use url::Url;
+
+const REQUEST_TIMEOUT_SECONDS: u64 = 10;
+
+static CLIENT: LazyLock<Client> = LazyLock::new(|| {
+  Client::builder()
+    .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECONDS))
+    .build()
+    .expect("Failed to build HTTP client")
+});
+
+/// Files at a templated URL, with the latest version read from `latest_version_url`.
+pub(crate) struct UrlProvider;
+
+#[async_trait]
+impl ReleaseProvider for UrlProvider {
+  /// Only the latest version is known, there is nothing to list the others from.
+  async fn list_versions(&self, app: &SupportedApp) -> Result<Vec<String>, AppError> {
+    Ok(vec![resolve_url_version(app, "latest").await?])
+  }
+
+  async fn list_assets(
+    &self,
+    app: &SupportedApp,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> Result<Vec<DownloadInfo>, AppError> {
+    get_url_download_links(app, target_deployment, version).await
+  }
+}
+
+/// Expands the app's `file_pattern` against its base URL and checks that the file exists.
+pub(crate) async fn get_url_download_links(
+  app: &SupportedApp,
+  target_deployment: &TargetDeployment,
+  version: &str,
+) -> Result<Vec<DownloadInfo>, AppError> {
+  let version = resolve_url_version(app, version).await?;
+  let url = expand_download_url(app, target_deployment, &version)?;
+  debug!("checking {} for {} {}", url, app.shortname, version);
+
+  let response = CLIENT.head(url.clone()).send().await?;
+  if !response.status().is_success() {
+    debug!("{} returned status {}", url, response.status());
+    return Ok(vec![]);
+  }
+
+  let content_type = response
+    .headers()
+    .get(CONTENT_TYPE)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.parse::<Mime>().ok())
+    .unwrap_or(mime::APPLICATION_OCTET_STREAM);
+  let size = response
+    .headers()
+    .get(CONTENT_LENGTH)
+    .and_then(|value| value.to_str().ok())
+    .and_then(|value| value.parse::<u64>().ok())
+    .unwrap_or(0);
+
+  Ok(vec![DownloadInfo::from_url(url, content_type, size)])
+}
+
+/// Turns `latest` into a concrete version using the app's `latest_version_url`.
+pub(crate) async fn resolve_url_version(
+  app: &SupportedApp,
+  version: &str,
+) -> Result<String, AppError> {
+  if version != "latest" {
+    return Ok(version.trim_start_matches('v').to_string());
+  }
+
+  let latest_url = app.latest_version_url.as_ref().ok_or_else(|| {
+    AppError::InvalidInput(format!(
+      "{} cannot resolve the latest version, pass an explicit version",
+      app.shortname
+    ))
+  })?;
+  let response = CLIENT.get(latest_url.clone()).send().await?;
+  if !response.status().is_success() {
+    return Err(AppError::Upstream(format!(
+      "{} returned status {}",
+      latest_url,
+      response.status()
+    )));
+  }
+  let body = response.text().await?;
+  let latest = body.trim().trim_start_matches('v');
+  if latest.is_empty() || latest.contains(char::is_whitespace) {
+    return Err(AppError::Upstream(format!(
+      "{} did not return a version",
+      latest_url
+    )));
+  }
+  Ok(latest.to_string())
+}
+
+pub(crate) fn expand_download_url(
+  app: &SupportedApp,
+  target_deployment: &TargetDeployment,
+  version: &str,
+) -> Result<Url, AppError> {
+  let pattern = app.file_pattern.as_deref().ok_or_else(|| {
+    AppError::InvalidInput(format!("{} has no file_pattern to expand", app.shortname))
+  })?;
+  let base = app.repo.get_url()?;
+  let path = app.render_pattern(pattern, version, target_deployment);
+  let url = format!(
+    "{}/{}",
+    base.as_str().trim_end_matches('/'),
+    path.trim_start_matches('/')
+  );
+  Url::parse(&url).map_err(|err| AppError::InvalidInput(format!("Invalid download URL: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::platform::{TargetArch, TargetOs};
+  use crate::providers::test_support::serve;
+  use crate::supported_apps::{load_registry, parse_registry, AppSource};
+  use axum::routing::get;
+  use axum::Router;
+
+  const HEADER: &str = "shortname,repo,source,file_pattern,archive_path,archive_depth,custom_release_tag,platform_names,latest_version_url\n";
+
+  fn app(row: &str) -> SupportedApp {
+    let apps = parse_registry(format!("{}{}\n", HEADER, row).as_bytes(), "test.csv").unwrap();
+    apps.into_values().next().unwrap()
+  }
+
+  async fn stub_server() -> String {
+    let router = Router::new()
+      .route("/helm-latest-version", get(|| async { "v3.16.2\n" }))
+      .route(
+        "/helm-v3.16.2-darwin-arm64.tar.gz",
+        get(|| async { ([("Content-Type", "application/x-tar")], "archive") }),
+      );
+    serve(router)
+      .await
+      .trim_start_matches("http://")
+      .to_string()
+  }
+
+  #[test]
+  fn expands_go_style_names_by_default() {
+    let terraform = app("terraform,releases.hashicorp.com,url,terraform/VERSION/terraform_VERSION_OS_ARCH.zip,terraform,0,-,-,-");
+    assert_eq!(terraform.source, AppSource::Url);
+    let url = expand_download_url(
+      &terraform,
+      &TargetDeployment::new(TargetOs::Mac, TargetArch::Aarch64),
+      "1.9.8",
+    )
+    .unwrap();
+    assert_eq!(
+      url.as_str(),
+      "https://releases.hashicorp.com/terraform/1.9.8/terraform_1.9.8_darwin_arm64.zip"
+    );
+  }
+
+  #[test]
+  fn kubectl_comes_from_the_kubernetes_release_bucket() {
+    let apps = load_registry("apps.csv").unwrap();
+    let kubectl = &apps["kubectl"];
+    assert_eq!(kubectl.source, AppSource::Url);
+    let url = expand_download_url(
+      kubectl,
+      &TargetDeployment::new(TargetOs::Linux, TargetArch::Arm64),
+      "1.31.2",
+    )
+    .unwrap();
+    assert_eq!(
+      url.as_str(),
+      "https://dl.k8s.io/release/v1.31.2/bin/linux/arm64/kubectl"
+    );
+  }
+
+  #[test]
+  fn expands_per_app_spellings() {
+    let tool = app("tool,example.com/downloads/,url,tool-VERSION-OS-ARCH.tar.gz,-,-,-,mac=macos;amd64=x86_64;linux=Linux,-");
+    let deployment = TargetDeployment::new(TargetOs::Mac, TargetArch::Amd64);
+    let url = expand_download_url(&tool, &deployment, "2.0.0").unwrap();
+    assert_eq!(
+      url.as_str(),
+      "https://example.com/downloads/tool-2.0.0-macos-x86_64.tar.gz"
+    );
+    let deployment = TargetDeployment::new(TargetOs::Linux, TargetArch::Arm64);
+    let url = expand_download_url(&tool, &deployment, "2.0.0").unwrap();
+    assert_eq!(
+      url.as_str(),
+      "https://example.com/downloads/tool-2.0.0-Linux-arm64.tar.gz"
+    );
+  }
+
+  #[tokio::test]
+  async fn resolves_latest_and_checks_the_file() {
+    let host = stub_server().await;
+    let mut helm = app("helm,get.helm.sh,url,helm-vVERSION-OS-ARCH.tar.gz,OS-ARCH/helm,1,-,-,-");
+    helm.repo = crate::supported_apps::Repo::Url(format!("http://{}", host));
+    helm.latest_version_url =
+      Some(Url::parse(&format!("http://{}/helm-latest-version", host)).unwrap());
+
+    let deployment = TargetDeployment::new(TargetOs::Mac, TargetArch::Arm64);
+    let links = get_url_download_links(&helm, &deployment, "latest")
+      .await
+      .unwrap();
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].name, "helm-v3.16.2-darwin-arm64.tar.gz");
+    assert_eq!(links[0].size, "archive".len() as u64);
+    assert_eq!(links[0].target.deployment, deployment);
+
+    let missing = get_url_download_links(&helm, &deployment, "v3.0.0")
+      .await
+      .unwrap();
+    assert!(missing.is_empty());
+  }
+}
@@ -0,0 +1,97 @@
+//! Keeps the config and app registry in step with their files while the server runs.
+//!
+//! Both files are polled for changes and reloaded on SIGHUP. A reload that fails to read or
+//! validate is logged and the previous state keeps serving requests.
+
+use crate::config::{self, CONFIG, CONFIG_PATH};
+use crate::supported_apps;
+use log::{error, info};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Starts the background tasks that watch for file changes and SIGHUP.
+pub(crate) fn spawn_watchers() {
+  tokio::spawn(watch_files());
+  #[cfg(unix)]
+  tokio::spawn(watch_sighup());
+}
+
+fn reload_config() {
+  match config::reload_config(CONFIG_PATH) {
+    Ok(()) => info!("config reloaded from {:?}", CONFIG_PATH),
+    Err(err) => error!(
+      "config reload failed, keeping the previous config: {:#}",
+      err
+    ),
+  }
+}
+
+fn reload_registry() {
+  let path = CONFIG.load().registry.path.clone();
+  match supported_apps::reload_registry(&path) {
+    Ok(count) => info!("app registry reloaded from {:?}: {} apps", path, count),
+    Err(err) => error!(
+      "app registry reload failed, keeping the previous registry: {:#}",
+      err
+    ),
+  }
+}
+
+fn modified(path: &str) -> Option<SystemTime> {
+  fs::metadata(path)
+    .and_then(|metadata| metadata.modified())
+    .ok()
+}
+
+async fn watch_files() {
+  let mut config_mtime = modified(CONFIG_PATH);
+  let mut registry_path = CONFIG.load().registry.path.clone();
+  let mut registry_mtime = modified(&registry_path);
+  let mut interval = tokio::time::interval(POLL_INTERVAL);
+  loop {
+    interval.tick().await;
+
+    let mtime = modified(CONFIG_PATH);
+    let config_changed = mtime != config_mtime;
+    config_mtime = mtime;
+    if config_changed && mtime.is_some() {
+      reload_config();
+    }
+
+    // the registry depends on the config (its path, the python index), so reload it after any
+    // config change as well as when its own file changes
+    let path = CONFIG.load().registry.path.clone();
+    let mtime = modified(&path);
+    if config_changed || path != registry_path || mtime != registry_mtime {
+      registry_path = path;
+      registry_mtime = mtime;
+      reload_registry();
+    }
+  }
+}
+
+#[cfg(unix)]
+async fn watch_sighup() {
+  use tokio::signal::unix::{signal, SignalKind};
+
+  let mut hangups = match signal(SignalKind::hangup()) {
+    Ok(hangups) => hangups,
+    Err(err) => {
+      error!(
+        "failed to listen for SIGHUP, reload on signal disabled: {}",
+        err
+      );
+      return;
+    }
+  };
+  while hangups.recv().await.is_some() {
+    info!("SIGHUP received, reloading config and app registry");
+    if Path::new(CONFIG_PATH).exists() {
+      reload_config();
+    }
+    reload_registry();
+  }
+}
@@ -0,0 +1,57 @@
+use crate::error::AppError;
+use crate::http::query::DEFAULT_VERSION;
+use crate::http::responses::{AppChecksums, AppInfo, AppPlatform};
+use crate::providers::{ProviderRegistry, PROVIDERS};
+use crate::supported_apps;
+use crate::supported_apps::SupportedApp;
+
+pub(crate) fn list_apps() -> Result<Vec<AppInfo>, AppError> {
+  supported_apps::all_apps().iter().map(app_info).collect()
+}
+
+pub(crate) fn describe_app(app: &str) -> Result<AppInfo, AppError> {
+  let supported_app =
+    supported_apps::get_app(app).ok_or_else(|| AppError::UnsupportedApp(app.to_string()))?;
+  app_info(&supported_app)
+}
+
+/// Digests published for `version` of a registry app, resolving `latest` first.
+pub(crate) async fn list_checksums(app: &str, version: &str) -> Result<AppChecksums, AppError> {
+  let supported_app =
+    supported_apps::get_app(app).ok_or_else(|| AppError::UnsupportedApp(app.to_string()))?;
+  list_checksums_with(&PROVIDERS, &supported_app, version).await
+}
+
+/// `list_checksums` against a specific set of providers.
+pub(crate) async fn list_checksums_with(
+  providers: &ProviderRegistry,
+  app: &SupportedApp,
+  version: &str,
+) -> Result<AppChecksums, AppError> {
+  let provider = providers.get(app.source)?;
+  let version = provider.resolve_version(app, version).await?;
+  let checksums = provider.fetch_checksums(app, &version).await?;
+  Ok(AppChecksums {
+    shortname: app.shortname.clone(),
+    version,
+    checksums: checksums.into_iter().collect(),
+  })
+}
+
+fn app_info(app: &SupportedApp) -> Result<AppInfo, AppError> {
+  Ok(AppInfo {
+    shortname: app.shortname.clone(),
+    source: app.source.to_string(),
+    repo_url: app.repo.html_url()?.to_string(),
+    description: app.description.clone(),
+    platforms: app
+      .platforms
+      .iter()
+      .map(|platform| AppPlatform {
+        os: platform.os.to_string(),
+        arch: platform.arch.to_string(),
+      })
+      .collect(),
+    default_version: DEFAULT_VERSION.to_string(),
+  })
+}
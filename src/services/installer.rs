@@ -1,12 +1,15 @@
-use crate::domain::platform::TargetDeployment;
+use crate::config::CONFIG;
+use crate::domain::platform::{TargetDeployment, TargetOs};
 use crate::error::AppError;
-use crate::http::query::InstallQueryOptions;
+use crate::http::query::{InstallMethod, InstallQueryOptions, DEFAULT_VERSION};
 use crate::http::responses::ScriptResponse;
-use crate::providers::gh::get_github_download_links;
+use crate::providers::cargo::{crate_belongs_to, get_cargo_package, get_crate_release};
+use crate::providers::gh::{get_github_release, get_go_module, has_release};
+use crate::providers::{ProviderRegistry, ReleaseProvider, PROVIDERS};
 use crate::services::templating;
 use crate::supported_apps;
-use crate::supported_apps::{DownloadInfo, Repo, SupportedApp};
-use log::debug;
+use crate::supported_apps::{AppSource, DownloadInfo, Repo, SupportedApp};
+use log::{debug, info};
 
 fn validate_github_path_segment(segment: &str, name: &str) -> Result<(), AppError> {
   if segment.is_empty() {
@@ -60,9 +63,16 @@ pub(crate) async fn build_supported_install_script(
 
   let supported_app =
     supported_apps::get_app(app).ok_or_else(|| AppError::UnsupportedApp(app.to_string()))?;
+  match query.method {
+    InstallMethod::Go => return build_go_install_script(&supported_app, query, html).await,
+    InstallMethod::Cargo => return build_cargo_install_script(&supported_app, query, html).await,
+    _ => {}
+  }
 
-  let (target, links) = load_app(query, &supported_app).await?;
-  let (script, extension) = templating::render_install_script(query, &links, &target.os)?;
+  let (script, extension) = match render_script(query, &supported_app).await {
+    Ok(rendered) => rendered,
+    Err(err) => return install_from_source(err, &supported_app, query, html).await,
+  };
 
   Ok(ScriptResponse::new(
     format!("install-{}.{}", supported_app.shortname, extension),
@@ -82,11 +92,248 @@ pub(crate) async fn build_arbitrary_github_install_script(
   validate_github_path_segment(repo, "repo")?;
 
   let app_name = format!("{}/{}", user, repo);
-  let target_app = SupportedApp::new(&app_name, Repo::github(&app_name), "github");
+  let target_app = SupportedApp::new(&app_name, Repo::github(&app_name), AppSource::Github);
+  build_github_repo_install_script(target_app, query, html).await
+}
+
+/// Like `build_arbitrary_github_install_script`, for a repo on a GitHub Enterprise Server host
+/// configured under `github.hosts`.
+pub(crate) async fn build_github_enterprise_install_script(
+  host: &str,
+  user: &str,
+  repo: &str,
+  query: &mut InstallQueryOptions,
+  html: bool,
+) -> Result<ScriptResponse, AppError> {
+  validate_github_path_segment(user, "user")?;
+  validate_github_path_segment(repo, "repo")?;
+  let config = CONFIG.load();
+  let host = config
+    .github
+    .host(host)
+    .ok_or_else(|| AppError::HostNotAllowed(host.to_string()))?;
+
+  let app_name = format!("{}/{}", user, repo);
+  let target_app = SupportedApp::new(
+    &app_name,
+    Repo::github_enterprise(host, &app_name),
+    AppSource::Github,
+  );
+  build_github_repo_install_script(target_app, query, html).await
+}
+
+async fn build_github_repo_install_script(
+  target_app: SupportedApp,
+  query: &mut InstallQueryOptions,
+  html: bool,
+) -> Result<ScriptResponse, AppError> {
+  query.set_app(target_app.shortname.clone());
+  match query.method {
+    InstallMethod::Go => return build_go_install_script(&target_app, query, html).await,
+    InstallMethod::Cargo => return build_cargo_install_script(&target_app, query, html).await,
+    _ => {}
+  }
+  let (script, extension) = match render_script(query, &target_app).await {
+    Ok(rendered) => rendered,
+    Err(err) => return install_from_source(err, &target_app, query, html).await,
+  };
+
+  Ok(ScriptResponse::new(
+    format!("install.{}", extension),
+    script,
+    query.inline,
+    html,
+  ))
+}
+
+/// The install script for an app and its file extension, from the provider for its source.
+async fn render_script(
+  query: &InstallQueryOptions,
+  app: &SupportedApp,
+) -> Result<(String, &'static str), AppError> {
+  PROVIDERS.get(app.source)?.render_script(app, query).await
+}
+
+/// Builds a GitHub-hosted Go module from source with `go install`, at the release tag for the
+/// requested version. Repos without any release are built at `latest`, which Go resolves from
+/// their tags itself.
+async fn build_go_install_script(
+  app: &SupportedApp,
+  query: &InstallQueryOptions,
+  html: bool,
+) -> Result<ScriptResponse, AppError> {
+  if app.source != AppSource::Github {
+    return Err(AppError::InvalidInput(format!(
+      "method=go is only available for GitHub repositories, {} is a {} app",
+      app.shortname, app.source
+    )));
+  }
+  let tag = if query.version == DEFAULT_VERSION {
+    match get_github_release(&app.repo, DEFAULT_VERSION).await {
+      Ok(release) => Some(release.tag),
+      Err(AppError::NoMatchingAssets { .. }) => None,
+      Err(err) => return Err(err),
+    }
+  } else {
+    Some(format!("v{}", app.version_from_tag(&query.version)))
+  };
+  let module = get_go_module(&app.repo, tag.as_deref())
+    .await?
+    .ok_or_else(|| {
+      AppError::InvalidInput(format!(
+        "{} has no go.mod, it cannot be built with method=go",
+        app.shortname
+      ))
+    })?;
+  debug!("building {} from go module {:?}", app.shortname, module);
+
+  let go_version = tag.as_deref().unwrap_or(DEFAULT_VERSION);
+  let (script, extension) = templating::render_go_install_script(query, &module, go_version)?;
+  Ok(ScriptResponse::new(
+    format!("install-{}.{}", module.binary_name(), extension),
+    script,
+    query.inline,
+    html,
+  ))
+}
+
+/// Installs a Rust crate with cargo-binstall or `cargo install`, at the version published on
+/// the configured index. The crate name comes from the repo's root `Cargo.toml`.
+async fn build_cargo_install_script(
+  app: &SupportedApp,
+  query: &InstallQueryOptions,
+  html: bool,
+) -> Result<ScriptResponse, AppError> {
+  if app.source != AppSource::Github {
+    return Err(AppError::InvalidInput(format!(
+      "method=cargo is only available for GitHub repositories, {} is a {} app",
+      app.shortname, app.source
+    )));
+  }
+  let no_package = || {
+    AppError::InvalidInput(format!(
+      "{} has no Cargo.toml package, it cannot be installed with method=cargo",
+      app.shortname
+    ))
+  };
+  let package = get_cargo_package(&app.repo).await?.ok_or_else(no_package)?;
+  let config = CONFIG.load();
+  if package.from_repo_name
+    && !crate_belongs_to(&config.cargo.index_url, &package.name, &app.repo).await?
+  {
+    return Err(no_package());
+  }
+  let release = get_crate_release(&config.cargo.index_url, &package.name, &query.version)
+    .await?
+    .ok_or_else(|| {
+      AppError::InvalidInput(format!(
+        "crate {} is not published on {}",
+        package.name, config.cargo.index_url
+      ))
+    })?;
+  debug!("installing {} from crate {:?}", app.shortname, release);
+
+  let index = config.cargo.cargo_index();
+  let (script, extension) =
+    templating::render_cargo_install_script(query, &release, package.binstall, index.as_deref())?;
+  Ok(ScriptResponse::new(
+    format!("install-{}.{}", release.name, extension),
+    script,
+    query.inline,
+    html,
+  ))
+}
+
+/// Falls back to a source install when a GitHub release exists but has no asset for the target.
+/// Published Rust crates are installed through cargo right away, Go modules are pointed at
+/// `method=go`. Missing releases, and registry apps whose `file_pattern` names their assets,
+/// keep the error.
+async fn install_from_source(
+  err: AppError,
+  app: &SupportedApp,
+  query: &InstallQueryOptions,
+  html: bool,
+) -> Result<ScriptResponse, AppError> {
+  match err {
+    AppError::NoMatchingAssets { repo, target }
+      if app.source == AppSource::Github && app.file_pattern.is_none() =>
+    {
+      if !has_release(app, &query.version).await? {
+        return Err(AppError::NoMatchingAssets { repo, target });
+      }
+      match build_cargo_install_script(app, query, html).await {
+        Ok(script) => {
+          info!(
+            "{} has no asset for {}, installing it with cargo",
+            repo, target
+          );
+          return Ok(script);
+        }
+        Err(err) => debug!("{} cannot be installed with cargo: {:?}", repo, err),
+      }
+      match get_go_module(&app.repo, None).await {
+        Ok(Some(_)) => Err(AppError::NoMatchingAssetsBuildable {
+          repo,
+          target,
+          method: InstallMethod::Go.to_string(),
+        }),
+        _ => Err(AppError::NoMatchingAssets { repo, target }),
+      }
+    }
+    other => Err(other),
+  }
+}
+
+pub(crate) async fn build_arbitrary_gitlab_install_script(
+  project: &str,
+  query: &mut InstallQueryOptions,
+  html: bool,
+) -> Result<ScriptResponse, AppError> {
+  let segments: Vec<&str> = project.split('/').collect();
+  if segments.len() < 2 {
+    return Err(AppError::InvalidInput(
+      "gitlab project must be in group/project form".to_string(),
+    ));
+  }
+  for segment in &segments {
+    validate_github_path_segment(segment, "project path segment")?;
+  }
+
+  let target_app = SupportedApp::new(project, Repo::gitlab(project), AppSource::Gitlab);
+
+  query.set_app(project.to_string());
+  let (script, extension) = render_script(query, &target_app).await?;
+
+  Ok(ScriptResponse::new(
+    format!("install.{}", extension),
+    script,
+    query.inline,
+    html,
+  ))
+}
+
+pub(crate) async fn build_arbitrary_forgejo_install_script(
+  host: &str,
+  owner: &str,
+  repo: &str,
+  query: &mut InstallQueryOptions,
+  html: bool,
+) -> Result<ScriptResponse, AppError> {
+  if !CONFIG.load().forgejo.is_allowed(host) {
+    return Err(AppError::HostNotAllowed(host.to_string()));
+  }
+  validate_github_path_segment(owner, "owner")?;
+  validate_github_path_segment(repo, "repo")?;
+
+  let app_name = format!("{}/{}", owner, repo);
+  let target_app = SupportedApp::new(
+    &app_name,
+    Repo::forgejo(host, owner, repo),
+    AppSource::Forgejo,
+  );
 
   query.set_app(app_name);
-  let (target, links) = load_app(query, &target_app).await?;
-  let (script, extension) = templating::render_install_script(query, &links, &target.os)?;
+  let (script, extension) = render_script(query, &target_app).await?;
 
   Ok(ScriptResponse::new(
     format!("install.{}", extension),
@@ -96,23 +343,238 @@ pub(crate) async fn build_arbitrary_github_install_script(
   ))
 }
 
+/// The assets found for an app, with the target and concrete version they were resolved for.
+#[derive(Debug)]
+pub(crate) struct LoadedApp {
+  pub(crate) target: TargetDeployment,
+  pub(crate) version: String,
+  pub(crate) links: Vec<DownloadInfo>,
+  /// Assets that matched the target before one was picked, above `links.len()` when the
+  /// app's `file_pattern` is ambiguous
+  pub(crate) candidates: usize,
+}
+
 pub(crate) async fn load_app(
   query: &InstallQueryOptions,
   supported_app: &SupportedApp,
-) -> Result<(TargetDeployment, Vec<DownloadInfo>), AppError> {
-  let arch = query.arch.clone();
-  let os = query.os.clone();
-  let version = query.version.clone();
-  let target_deployment = TargetDeployment::new(os, arch);
+) -> Result<LoadedApp, AppError> {
+  load_app_with(&PROVIDERS, query, supported_app).await
+}
+
+/// `load_app` against a specific set of providers.
+pub(crate) async fn load_app_with(
+  providers: &ProviderRegistry,
+  query: &InstallQueryOptions,
+  supported_app: &SupportedApp,
+) -> Result<LoadedApp, AppError> {
+  load_app_from(providers.get(supported_app.source)?, query, supported_app).await
+}
+
+/// `load_app` against the provider for the app's source.
+pub(crate) async fn load_app_from<P: ReleaseProvider + ?Sized>(
+  provider: &P,
+  query: &InstallQueryOptions,
+  supported_app: &SupportedApp,
+) -> Result<LoadedApp, AppError> {
+  let target_deployment =
+    TargetDeployment::new(query.os.clone(), query.arch.clone()).with_libc(query.libc.clone());
   debug!("target_deployment loaded: {:#?}", target_deployment);
 
-  let links = get_github_download_links(&supported_app.repo, &target_deployment, &version).await?;
+  let version = provider
+    .resolve_version(supported_app, &query.version)
+    .await?;
+  let assets = provider
+    .list_assets(supported_app, &version, &target_deployment)
+    .await?;
+  let candidates = assets.len();
+  let mut links = if provider.platform_specific() {
+    supported_app.pick_asset(assets, &target_deployment)
+  } else {
+    assets
+  };
+  // providers rank their assets best first
+  if query.auto_select {
+    if target_deployment.os == TargetOs::Linux && target_deployment.libc.is_none() {
+      // the script picks between the best build for each libc once it knows the host's
+      let mut kept = vec![];
+      links.retain(|link| {
+        let libc = link.target.deployment.libc.clone();
+        let first = !kept.contains(&libc);
+        kept.push(libc);
+        first
+      });
+    } else {
+      links.truncate(1);
+    }
+  }
   if links.is_empty() {
     return Err(AppError::NoMatchingAssets {
       repo: supported_app.shortname.clone(),
       target: target_deployment.to_string(),
     });
   }
+  let links = links
+    .into_iter()
+    .map(|link| DownloadInfo {
+      version: Some(version.clone()),
+      ..link
+    })
+    .collect();
+
+  Ok(LoadedApp {
+    target: target_deployment,
+    version,
+    links,
+    candidates,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::platform::{TargetArch, TargetOs};
+  use crate::providers::{target_assets, ReleaseProvider};
+  use async_trait::async_trait;
+  use url::Url;
+
+  /// Serves two releases, each with a binary and a checksum per platform.
+  struct FakeProvider;
+
+  #[async_trait]
+  impl ReleaseProvider for FakeProvider {
+    async fn list_versions(&self, _app: &SupportedApp) -> Result<Vec<String>, AppError> {
+      Ok(vec!["2.0.0".to_string(), "1.0.0".to_string()])
+    }
+
+    async fn list_assets(
+      &self,
+      app: &SupportedApp,
+      version: &str,
+      target_deployment: &TargetDeployment,
+    ) -> Result<Vec<DownloadInfo>, AppError> {
+      let assets = ["linux_amd64", "linux_amd64.tar.gz", "darwin_arm64"]
+        .iter()
+        .map(|suffix| {
+          let name = format!("tool_{}_{}", version, suffix);
+          let url = Url::parse(&format!("https://example.com/{}", name)).unwrap();
+          DownloadInfo::from_named_url(name, url, mime::APPLICATION_OCTET_STREAM, 1024)
+        })
+        .collect();
+      app.match_assets(assets, version, target_deployment)
+    }
+  }
+
+  /// Serves one release and leaves ordering its assets to the ranking.
+  struct RankedProvider;
+
+  #[async_trait]
+  impl ReleaseProvider for RankedProvider {
+    async fn list_versions(&self, _app: &SupportedApp) -> Result<Vec<String>, AppError> {
+      Ok(vec!["1.0.0".to_string()])
+    }
 
-  Ok((target_deployment, links))
+    async fn list_assets(
+      &self,
+      app: &SupportedApp,
+      version: &str,
+      target_deployment: &TargetDeployment,
+    ) -> Result<Vec<DownloadInfo>, AppError> {
+      let assets = [
+        "tool-linux-amd64-debug.tar.gz",
+        "tool-linux-amd64",
+        "tool-linux-amd64.tar.gz",
+        "tool-darwin-arm64.tar.gz",
+      ]
+      .iter()
+      .map(|name| {
+        let url = Url::parse(&format!("https://example.com/{}", name)).unwrap();
+        DownloadInfo::from_url(url, mime::APPLICATION_OCTET_STREAM, 1024 * 1024)
+      })
+      .collect();
+      target_assets(app, assets, version, target_deployment)
+    }
+  }
+
+  fn query(version: &str) -> InstallQueryOptions {
+    InstallQueryOptions::new(
+      Some("tool".to_string()),
+      Some(version.to_string()),
+      None,
+      Some(TargetArch::Amd64),
+      Some(TargetOs::Linux),
+      None,
+      None,
+      None,
+      None,
+      None,
+      None,
+    )
+  }
+
+  #[tokio::test]
+  async fn loads_apps_through_injected_providers() {
+    let providers = ProviderRegistry::new().with(AppSource::Github, FakeProvider);
+    let mut app = SupportedApp::new("tool", Repo::github("owner/tool"), AppSource::Github);
+    app.file_pattern = Some("tool_VERSION_OS_ARCH*".to_string());
+
+    let latest = load_app_with(&providers, &query("latest"), &app)
+      .await
+      .unwrap();
+    assert_eq!(latest.version, "2.0.0");
+    assert_eq!(latest.candidates, 2);
+    assert_eq!(latest.links.len(), 1);
+    assert_eq!(latest.links[0].name, "tool_2.0.0_linux_amd64");
+    assert_eq!(latest.links[0].version.as_deref(), Some("2.0.0"));
+
+    let pinned = load_app_with(&providers, &query("v1.0.0"), &app)
+      .await
+      .unwrap();
+    assert_eq!(pinned.links[0].name, "tool_1.0.0_linux_amd64");
+
+    let unregistered = SupportedApp::new("tool", Repo::gitlab("owner/tool"), AppSource::Gitlab);
+    let err = load_app_with(&providers, &query("latest"), &unregistered)
+      .await
+      .unwrap_err();
+    assert!(matches!(err, AppError::InvalidInput(_)));
+  }
+
+  #[tokio::test]
+  async fn auto_select_keeps_the_best_ranked_asset() {
+    let providers = ProviderRegistry::new().with(AppSource::Github, RankedProvider);
+    let app = SupportedApp::new("tool", Repo::github("owner/tool"), AppSource::Github);
+
+    let ranked = load_app_with(&providers, &query("latest"), &app)
+      .await
+      .unwrap();
+    let names: Vec<&str> = ranked.links.iter().map(|link| link.name.as_str()).collect();
+    assert_eq!(
+      names,
+      [
+        "tool-linux-amd64.tar.gz",
+        "tool-linux-amd64",
+        "tool-linux-amd64-debug.tar.gz"
+      ]
+    );
+    assert!(ranked.links[0].score > ranked.links[1].score);
+
+    let mut auto = query("latest");
+    auto.auto_select = true;
+    let selected = load_app_with(&providers, &auto, &app).await.unwrap();
+    assert_eq!(selected.candidates, 3);
+    assert_eq!(selected.links.len(), 1);
+    assert_eq!(selected.links[0].name, "tool-linux-amd64.tar.gz");
+  }
+
+  #[tokio::test]
+  async fn apps_with_a_file_pattern_are_not_built_from_source() {
+    let mut app = SupportedApp::new("tool", Repo::github("owner/tool"), AppSource::Github);
+    app.file_pattern = Some("tool-OS-ARCH.tar.gz".to_string());
+    let missing = AppError::NoMatchingAssets {
+      repo: "tool".to_string(),
+      target: "linux-amd64".to_string(),
+    };
+
+    let result = install_from_source(missing, &app, &query("latest"), false).await;
+    assert!(matches!(result, Err(AppError::NoMatchingAssets { .. })));
+  }
 }
@@ -1,2 +1,4 @@
+pub(crate) mod catalog;
 pub(crate) mod installer;
+pub(crate) mod registry_check;
 pub(crate) mod templating;
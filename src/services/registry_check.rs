@@ -0,0 +1,295 @@
+use crate::domain::platform::TargetDeployment;
+use crate::error::AppError;
+use crate::providers::PROVIDERS;
+use crate::services::installer::LoadedApp;
+use crate::supported_apps::{ArchiveBinary, SupportedApp};
+use globset::Glob;
+use serde::Serialize;
+use std::fmt::Write;
+
+/// Notes are clipped in the table, upstream errors can carry whole backtraces.
+const NOTES_WIDTH: usize = 100;
+
+/// How many assets an app resolved to for one platform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum AssetCount {
+  One,
+  Several,
+  None,
+  Error,
+}
+
+impl AssetCount {
+  fn as_str(&self) -> &'static str {
+    match self {
+      AssetCount::One => "one",
+      AssetCount::Several => "several",
+      AssetCount::None => "none",
+      AssetCount::Error => "error",
+    }
+  }
+}
+
+/// Outcome of resolving one registry app for one platform.
+#[derive(Debug, Serialize)]
+pub(crate) struct TargetCheck {
+  pub(crate) app: String,
+  pub(crate) platform: String,
+  pub(crate) version: Option<String>,
+  pub(crate) status: AssetCount,
+  pub(crate) assets: Vec<String>,
+  /// `archive_path` entries missing from the selected archive
+  pub(crate) missing_archive_paths: Vec<String>,
+  pub(crate) notes: Vec<String>,
+  pub(crate) regression: bool,
+}
+
+impl TargetCheck {
+  /// Classifies the result of `installer::load_app`. Apps with a `file_pattern` must resolve to
+  /// exactly one asset; apps without one are expected to offer a choice.
+  pub(crate) fn new(
+    app: &SupportedApp,
+    target: &TargetDeployment,
+    loaded: &Result<LoadedApp, AppError>,
+  ) -> TargetCheck {
+    let platform_specific = PROVIDERS
+      .get(app.source)
+      .map(|provider| provider.platform_specific())
+      .unwrap_or(true);
+    let platform = match platform_specific {
+      true => format!("{}/{}", target.os, target.arch),
+      false => "any".to_string(),
+    };
+    let mut check = TargetCheck {
+      app: app.shortname.clone(),
+      platform,
+      version: None,
+      status: AssetCount::Error,
+      assets: vec![],
+      missing_archive_paths: vec![],
+      notes: vec![],
+      regression: true,
+    };
+    match loaded {
+      Ok(loaded) => {
+        check.version = Some(loaded.version.clone());
+        check.assets = loaded.links.iter().map(|link| link.name.clone()).collect();
+        check.status = match loaded.candidates {
+          0 => AssetCount::None,
+          1 => AssetCount::One,
+          _ => AssetCount::Several,
+        };
+        let expects_one = app.file_pattern.is_some() && platform_specific;
+        check.regression = match check.status {
+          AssetCount::One => false,
+          AssetCount::Several => expects_one,
+          AssetCount::None | AssetCount::Error => true,
+        };
+        if check.status == AssetCount::Several && expects_one {
+          check
+            .notes
+            .push(format!("file_pattern matched {} assets", loaded.candidates));
+        }
+      }
+      Err(AppError::NoMatchingAssets { .. }) => {
+        check.status = AssetCount::None;
+        if app.file_pattern.is_some() {
+          check
+            .notes
+            .push("file_pattern matched no assets".to_string());
+        }
+      }
+      Err(err) => check.notes.push(err.message()),
+    }
+    check
+  }
+
+  /// Flags the `archive_path` entries that are not in the archive listing.
+  pub(crate) fn check_archive(&mut self, binaries: &[ArchiveBinary], entries: &[String]) {
+    self.missing_archive_paths = missing_archive_paths(binaries, entries);
+    if !self.missing_archive_paths.is_empty() {
+      self
+        .notes
+        .push("archive_path not found in archive".to_string());
+      self.regression = true;
+    }
+  }
+}
+
+fn missing_archive_paths(binaries: &[ArchiveBinary], entries: &[String]) -> Vec<String> {
+  binaries
+    .iter()
+    .filter(|binary| {
+      let Ok(glob) = Glob::new(&binary.archive_path) else {
+        return true;
+      };
+      let matcher = glob.compile_matcher();
+      // Windows archives add .exe to the binaries' names
+      !entries.iter().any(|entry| {
+        let entry = entry.trim_end_matches('/');
+        matcher.is_match(entry) || matcher.is_match(entry.trim_end_matches(".exe"))
+      })
+    })
+    .map(|binary| binary.archive_path.clone())
+    .collect()
+}
+
+fn clip(text: &str, width: usize) -> String {
+  let line = text.lines().next().unwrap_or_default();
+  if line.chars().count() > width || line.len() < text.len() {
+    format!("{}...", line.chars().take(width).collect::<String>())
+  } else {
+    line.to_string()
+  }
+}
+
+/// Renders the checks as an aligned plain-text table, one row per app and platform.
+pub(crate) fn render_table(checks: &[TargetCheck]) -> String {
+  let header = ["APP", "PLATFORM", "VERSION", "STATUS", "ASSETS", "NOTES"];
+  let rows: Vec<[String; 6]> = checks
+    .iter()
+    .map(|check| {
+      let mut notes = check.notes.clone();
+      notes.extend(
+        check
+          .missing_archive_paths
+          .iter()
+          .map(|path| format!("missing {}", path)),
+      );
+      [
+        check.app.clone(),
+        check.platform.clone(),
+        check.version.clone().unwrap_or_else(|| "-".to_string()),
+        if check.regression {
+          format!("{} (regression)", check.status.as_str())
+        } else {
+          check.status.as_str().to_string()
+        },
+        if check.assets.is_empty() {
+          "-".to_string()
+        } else {
+          check.assets.join(", ")
+        },
+        clip(&notes.join("; "), NOTES_WIDTH),
+      ]
+    })
+    .collect();
+
+  let mut widths = header.map(str::len);
+  for row in &rows {
+    for (width, cell) in widths.iter_mut().zip(row) {
+      *width = (*width).max(cell.len());
+    }
+  }
+
+  let mut table = String::new();
+  let header = header.map(str::to_string);
+  for row in std::iter::once(&header).chain(&rows) {
+    let line = row
+      .iter()
+      .zip(widths)
+      .map(|(cell, width)| format!("{cell:<width$}"))
+      .collect::<Vec<_>>()
+      .join("  ");
+    let _ = writeln!(table, "{}", line.trim_end());
+  }
+  table
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::platform::{TargetArch, TargetOs};
+  use crate::supported_apps::{load_registry, DownloadInfo};
+  use mime::APPLICATION_OCTET_STREAM;
+  use url::Url;
+
+  fn loaded(names: &[&str], candidates: usize) -> Result<LoadedApp, AppError> {
+    Ok(LoadedApp {
+      target: TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64),
+      version: "1.0.0".to_string(),
+      links: names
+        .iter()
+        .map(|name| {
+          let url = Url::parse(&format!("https://example.com/{}", name)).unwrap();
+          DownloadInfo::from_url(url, APPLICATION_OCTET_STREAM, 1)
+        })
+        .collect(),
+      candidates,
+    })
+  }
+
+  #[test]
+  fn ambiguous_or_missing_patterns_are_regressions() {
+    let apps = load_registry("apps.csv").unwrap();
+    let linux = TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64);
+
+    let one = TargetCheck::new(&apps["yq"], &linux, &loaded(&["yq_linux_amd64"], 1));
+    assert_eq!(one.status, AssetCount::One);
+    assert!(!one.regression);
+
+    let several = TargetCheck::new(&apps["yq"], &linux, &loaded(&["yq_linux_amd64"], 2));
+    assert_eq!(several.status, AssetCount::Several);
+    assert!(several.regression);
+    assert_eq!(several.notes, ["file_pattern matched 2 assets"]);
+
+    let none = TargetCheck::new(
+      &apps["shfmt"],
+      &linux,
+      &Err(AppError::NoMatchingAssets {
+        repo: "shfmt".to_string(),
+        target: linux.to_string(),
+      }),
+    );
+    assert_eq!(none.status, AssetCount::None);
+    assert!(none.regression);
+
+    let failed = TargetCheck::new(
+      &apps["yq"],
+      &linux,
+      &Err(AppError::UpstreamGithub("rate limited".to_string())),
+    );
+    assert_eq!(failed.status, AssetCount::Error);
+    assert!(failed.regression);
+
+    // pip apps offer sdists and wheels alike
+    let pip = TargetCheck::new(&apps["glances"], &linux, &loaded(&["a.whl", "a.tar.gz"], 2));
+    assert_eq!(pip.platform, "any");
+    assert!(!pip.regression);
+  }
+
+  #[test]
+  fn missing_archive_paths_are_regressions() {
+    let apps = load_registry("apps.csv").unwrap();
+    let uv = &apps["uv"];
+    let linux = TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64);
+    let binaries = uv.archive_binaries("0.5.4", &linux);
+    let mut check = TargetCheck::new(uv, &linux, &loaded(&["uv.tar.gz"], 1));
+
+    check.check_archive(
+      &binaries,
+      &[
+        "uv-x86_64-unknown-linux-musl/".to_string(),
+        "uv-x86_64-unknown-linux-musl/uv".to_string(),
+        "uv-x86_64-unknown-linux-musl/uvx".to_string(),
+      ],
+    );
+    assert!(!check.regression);
+
+    check.check_archive(&binaries, &["uv-x86_64-unknown-linux-musl/uv".to_string()]);
+    assert_eq!(
+      check.missing_archive_paths,
+      ["uv-x86_64-unknown-linux-musl/uvx"]
+    );
+    assert!(check.regression);
+
+    let table = render_table(&[check]);
+    assert!(
+      table.starts_with("APP  PLATFORM     VERSION  STATUS"),
+      "{}",
+      table
+    );
+    assert!(table.contains("missing uv-x86_64-unknown-linux-musl/uvx"));
+  }
+}
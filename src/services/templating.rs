@@ -1,22 +1,51 @@
-use crate::domain::platform::TargetOs;
+use crate::domain::platform::{TargetDeployment, TargetOs};
 use crate::error::AppError;
 use crate::http::query::InstallQueryOptions;
-use crate::supported_apps::DownloadInfo;
+use crate::providers::cargo::CrateRelease;
+use crate::providers::gh::GoModule;
+use crate::providers::npm::NpmRelease;
+use crate::providers::pypi::PythonRelease;
+use crate::services::installer::LoadedApp;
+use crate::supported_apps::SupportedApp;
 use crate::templates::TEMPLATES;
-use serde_json::Value;
+use serde_json::{json, Value};
 use tera::Context;
 
 pub(crate) fn render_install_script(
   query: &InstallQueryOptions,
-  links: &[DownloadInfo],
-  os: &TargetOs,
+  loaded: &LoadedApp,
+  app: &SupportedApp,
 ) -> Result<(String, &'static str), AppError> {
-  let json_links: Vec<Value> = links.iter().map(|x| x.json()).collect();
+  let json_links: Vec<Value> = loaded.links.iter().map(|x| x.json()).collect();
   let mut globals = query.template_globals();
   globals.insert("assets".to_string(), Value::Array(json_links));
+  globals.insert("version".to_string(), Value::from(loaded.version.as_str()));
+  // an emulated build keeps its binaries under its own arch's paths
+  let binaries_target = match loaded.links.first().and_then(|link| link.fallback.clone()) {
+    Some(arch) => TargetDeployment {
+      arch,
+      ..loaded.target.clone()
+    },
+    None => loaded.target.clone(),
+  };
+  let binaries: Vec<Value> = app
+    .archive_binaries(&loaded.version, &binaries_target)
+    .into_iter()
+    .map(|binary| {
+      json!({
+        "archive_path": binary.archive_path,
+        "install_name": binary.install_name,
+      })
+    })
+    .collect();
+  globals.insert("binaries".to_string(), Value::Array(binaries));
+  globals.insert(
+    "archive_depth".to_string(),
+    Value::from(app.archive_depth_for(&binaries_target.os).unwrap_or(0)),
+  );
   let tera_context = Context::from_serialize(globals)?;
 
-  let rendered = match os {
+  let rendered = match loaded.target.os {
     TargetOs::Windows => (TEMPLATES.render("install.ps1", &tera_context)?, "ps1"),
     TargetOs::Linux => (TEMPLATES.render("install.sh", &tera_context)?, "sh"),
     _ => (TEMPLATES.render("install.sh", &tera_context)?, "sh"),
@@ -24,3 +53,329 @@ pub(crate) fn render_install_script(
 
   Ok(rendered)
 }
+
+pub(crate) fn render_python_install_script(
+  query: &InstallQueryOptions,
+  release: &PythonRelease,
+  os: &TargetOs,
+) -> Result<(String, &'static str), AppError> {
+  let mut globals = query.template_globals();
+  globals.insert("package".to_string(), Value::from(release.package.as_str()));
+  globals.insert("version".to_string(), Value::from(release.version.as_str()));
+  globals.insert(
+    "index_url".to_string(),
+    Value::from(release.index_url.as_str()),
+  );
+  let tera_context = Context::from_serialize(globals)?;
+
+  let rendered = match os {
+    TargetOs::Windows => (
+      TEMPLATES.render("install_python.ps1", &tera_context)?,
+      "ps1",
+    ),
+    _ => (TEMPLATES.render("install_python.sh", &tera_context)?, "sh"),
+  };
+
+  Ok(rendered)
+}
+
+/// Renders a script that installs an npm package's tarball into the prefix with `npm install -g`.
+pub(crate) fn render_npm_install_script(
+  query: &InstallQueryOptions,
+  release: &NpmRelease,
+) -> Result<(String, &'static str), AppError> {
+  let mut globals = query.template_globals();
+  globals.insert("package".to_string(), Value::from(release.package.as_str()));
+  globals.insert("version".to_string(), Value::from(release.version.as_str()));
+  globals.insert(
+    "tarball".to_string(),
+    Value::from(release.tarball.url.as_str()),
+  );
+  globals.insert(
+    "registry_url".to_string(),
+    Value::from(release.registry_url.as_str()),
+  );
+  globals.insert(
+    "binaries".to_string(),
+    Value::from(release.binaries.clone()),
+  );
+  let tera_context = Context::from_serialize(globals)?;
+
+  let rendered = match query.os {
+    TargetOs::Windows => (TEMPLATES.render("install_npm.ps1", &tera_context)?, "ps1"),
+    _ => (TEMPLATES.render("install_npm.sh", &tera_context)?, "sh"),
+  };
+
+  Ok(rendered)
+}
+
+/// Renders a script that builds `module.package` with `go install` at `go_version`.
+pub(crate) fn render_go_install_script(
+  query: &InstallQueryOptions,
+  module: &GoModule,
+  go_version: &str,
+) -> Result<(String, &'static str), AppError> {
+  let mut globals = query.template_globals();
+  globals.insert("package".to_string(), Value::from(module.package.as_str()));
+  globals.insert("go_version".to_string(), Value::from(go_version));
+  globals.insert("binary_name".to_string(), Value::from(module.binary_name()));
+  let tera_context = Context::from_serialize(globals)?;
+
+  let rendered = match query.os {
+    TargetOs::Windows => (TEMPLATES.render("install_go.ps1", &tera_context)?, "ps1"),
+    _ => (TEMPLATES.render("install_go.sh", &tera_context)?, "sh"),
+  };
+
+  Ok(rendered)
+}
+
+/// Renders a script that installs a published crate with cargo-binstall or `cargo install`.
+pub(crate) fn render_cargo_install_script(
+  query: &InstallQueryOptions,
+  release: &CrateRelease,
+  binstall: bool,
+  index: Option<&str>,
+) -> Result<(String, &'static str), AppError> {
+  let mut globals = query.template_globals();
+  globals.insert("crate_name".to_string(), Value::from(release.name.as_str()));
+  globals.insert("version".to_string(), Value::from(release.version.as_str()));
+  globals.insert("binstall".to_string(), Value::from(binstall));
+  globals.insert("index".to_string(), Value::from(index.unwrap_or("")));
+  let tera_context = Context::from_serialize(globals)?;
+
+  let rendered = match query.os {
+    TargetOs::Windows => (TEMPLATES.render("install_cargo.ps1", &tera_context)?, "ps1"),
+    _ => (TEMPLATES.render("install_cargo.sh", &tera_context)?, "sh"),
+  };
+
+  Ok(rendered)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::platform::{TargetArch, TargetDeployment, TargetLibc};
+  use crate::supported_apps::{load_registry, AppSource, DownloadInfo, Repo};
+  use mime::APPLICATION_OCTET_STREAM;
+  use url::Url;
+
+  fn query() -> InstallQueryOptions {
+    let mut query = InstallQueryOptions::new(
+      None, None, None, None, None, None, None, None, None, None, None,
+    );
+    query.set_app("yq".to_string());
+    query
+  }
+
+  fn loaded(os: TargetOs, names: &[&str]) -> LoadedApp {
+    let links = names
+      .iter()
+      .map(|name| {
+        let url = Url::parse(&format!("https://example.com/{}", name)).unwrap();
+        DownloadInfo::from_url(url, APPLICATION_OCTET_STREAM, 1)
+      })
+      .collect();
+    LoadedApp {
+      target: TargetDeployment::new(os, TargetArch::Amd64),
+      version: "2.62.0".to_string(),
+      links,
+      candidates: names.len(),
+    }
+  }
+
+  #[test]
+  fn single_asset_skips_the_picker() {
+    let app = SupportedApp::new("yq", Repo::github("mikefarah/yq"), AppSource::Github);
+    for os in [TargetOs::Linux, TargetOs::Windows] {
+      let (script, _) =
+        render_install_script(&query(), &loaded(os.clone(), &["yq_linux_amd64"]), &app).unwrap();
+      assert!(
+        !script.contains("Please select one of the following"),
+        "{}",
+        script
+      );
+
+      let (script, _) = render_install_script(
+        &query(),
+        &loaded(os, &["yq_linux_amd64", "yq_linux_amd64.tar.gz"]),
+        &app,
+      )
+      .unwrap();
+      assert!(script.contains("Please select one of the following"));
+    }
+  }
+
+  #[test]
+  fn archive_paths_are_rendered_for_the_resolved_release() {
+    let apps = load_registry("apps.csv").unwrap();
+    let (script, _) = render_install_script(
+      &query(),
+      &loaded(TargetOs::Linux, &["gh_2.62.0_linux_amd64.tar.gz"]),
+      &apps["gh"],
+    )
+    .unwrap();
+    assert!(script.contains("_ARCHIVE_PATHS=( gh_2.62.0_linux_amd64/bin/gh )\n"));
+    assert!(script.contains("_INSTALL_NAMES=( gh )\n"));
+    assert!(script.contains("_ARCHIVE_DEPTH=2\n"));
+
+    let (script, _) = render_install_script(
+      &query(),
+      &loaded(TargetOs::Windows, &["gh_2.62.0_windows_amd64.zip"]),
+      &apps["gh"],
+    )
+    .unwrap();
+    // the Windows zip has bin/ at its root
+    assert!(script.contains("$_ARCHIVE_PATHS = @('bin/gh')"));
+    assert!(script.contains("$_ARCHIVE_DEPTH = 1\n"));
+  }
+
+  #[test]
+  fn every_binary_is_rendered() {
+    let apps = load_registry("apps.csv").unwrap();
+    let (script, _) = render_install_script(
+      &query(),
+      &loaded(TargetOs::Linux, &["uv-x86_64-unknown-linux-musl.tar.gz"]),
+      &apps["uv"],
+    )
+    .unwrap();
+    assert!(script.contains(
+      "_ARCHIVE_PATHS=( uv-x86_64-unknown-linux-musl/uv uv-x86_64-unknown-linux-musl/uvx )\n"
+    ));
+    assert!(script.contains("_INSTALL_NAMES=( uv uvx )\n"));
+
+    let mut gnu = loaded(TargetOs::Linux, &["uv-x86_64-unknown-linux-gnu.tar.gz"]);
+    gnu.target.libc = Some(TargetLibc::Gnu);
+    let (script, _) = render_install_script(&query(), &gnu, &apps["uv"]).unwrap();
+    assert!(script.contains(
+      "_ARCHIVE_PATHS=( uv-x86_64-unknown-linux-gnu/uv uv-x86_64-unknown-linux-gnu/uvx )\n"
+    ));
+
+    let (script, _) = render_install_script(
+      &query(),
+      &loaded(TargetOs::Windows, &["uv-x86_64-pc-windows-msvc.zip"]),
+      &apps["uv"],
+    )
+    .unwrap();
+    assert!(script.contains("$_INSTALL_NAMES = @('uv', 'uvx')"));
+    // with the binaries at the root of the zip
+    assert!(script.contains("$_ARCHIVE_PATHS = @('uv', 'uvx')"));
+    assert!(script.contains("$_ARCHIVE_DEPTH = 0\n"));
+  }
+
+  #[test]
+  fn libc_of_each_asset_is_rendered_for_the_host_check() {
+    let app = SupportedApp::new("uv", Repo::github("astral-sh/uv"), AppSource::Github);
+    let loaded = loaded(
+      TargetOs::Linux,
+      &[
+        "uv-x86_64-unknown-linux-musl.tar.gz",
+        "uv-x86_64-unknown-linux-gnu.tar.gz",
+      ],
+    );
+    let mut query = query();
+    let (script, _) = render_install_script(&query, &loaded, &app).unwrap();
+    assert!(script.contains("_libcs=( musl gnu )\n"));
+    assert!(script.contains("_LIBC=''\n"));
+    assert!(script.contains("_AUTO_SELECT=false\n"));
+
+    query.libc = Some(TargetLibc::Musl);
+    query.auto_select = true;
+    let (script, _) = render_install_script(&query, &loaded, &app).unwrap();
+    assert!(script.contains("_LIBC=musl\n"));
+    assert!(script.contains("_AUTO_SELECT=true\n"));
+
+    // an explicit none is not left to the script
+    query.libc = Some(TargetLibc::None);
+    let (script, _) = render_install_script(&query, &loaded, &app).unwrap();
+    assert!(script.contains("_LIBC=none\n"));
+  }
+
+  #[test]
+  fn emulated_builds_are_labelled_and_keep_their_paths() {
+    let apps = load_registry("apps.csv").unwrap();
+    let mut loaded = loaded(TargetOs::Mac, &["uv-x86_64-apple-darwin.tar.gz"]);
+    loaded.target.arch = TargetArch::Arm64;
+    loaded.links[0].fallback = Some(TargetArch::Amd64);
+    let (script, _) = render_install_script(&query(), &loaded, &apps["uv"]).unwrap();
+    assert!(
+      script.contains("_ARCHIVE_PATHS=( uv-x86_64-apple-darwin/uv uv-x86_64-apple-darwin/uvx )\n")
+    );
+    assert!(
+      script.contains("'uv-x86_64-apple-darwin.tar.gz (tar.gz, amd64 under emulation)'"),
+      "{}",
+      script
+    );
+  }
+
+  #[test]
+  fn published_digests_are_rendered_for_verification() {
+    let app = SupportedApp::new("yq", Repo::github("mikefarah/yq"), AppSource::Github);
+    let digest = "6e3bd5c8ab1f7d2c4e9a0b3f5d7c9e1a2b4d6f8a0c2e4b6d8f0a1c3e5b7d9f1a";
+    for os in [TargetOs::Linux, TargetOs::Windows] {
+      let mut loaded = loaded(os.clone(), &["yq_linux_amd64", "yq_linux_amd64.tar.gz"]);
+      loaded.links[1].sha256 = Some(digest.to_string());
+      let (script, _) = render_install_script(&query(), &loaded, &app).unwrap();
+      match os {
+        TargetOs::Windows => {
+          assert!(script.contains(&format!("$_sha256s = @(\"\", \"{}\")", digest)))
+        }
+        _ => assert!(script.contains(&format!("_sha256s=( '' {} )", digest))),
+      }
+    }
+  }
+
+  #[test]
+  fn go_install_uses_gobin_under_the_prefix() {
+    let module = GoModule {
+      module: "github.com/owner/tool/v2".to_string(),
+      package: "github.com/owner/tool/v2/cmd/tool".to_string(),
+    };
+    let (script, extension) = render_go_install_script(&query(), &module, "v2.1.0").unwrap();
+    assert_eq!(extension, "sh");
+    assert!(script.contains("_PACKAGE=github.com/owner/tool/v2/cmd/tool\n"));
+    assert!(script.contains("_VERSION=v2.1.0\n"));
+    assert!(script.contains("_BINARY_NAME=tool\n"));
+    assert!(script.contains(r#"GOBIN="$_BIN_DIR" go install "$_PACKAGE@$_VERSION""#));
+
+    let mut windows = query();
+    windows.os = TargetOs::Windows;
+    let (script, extension) = render_go_install_script(&windows, &module, "latest").unwrap();
+    assert_eq!(extension, "ps1");
+    assert!(script.contains("$_VERSION = 'latest'"));
+    assert!(script.contains("$env:GOBIN = $_BIN_DIR"));
+  }
+
+  #[test]
+  fn cargo_install_passes_custom_indexes_on() {
+    let release = CrateRelease {
+      name: "ripgrep".to_string(),
+      version: "14.1.0".to_string(),
+    };
+    let (script, _) = render_cargo_install_script(&query(), &release, true, None).unwrap();
+    assert!(script.contains("_CRATE=ripgrep\n"));
+    assert!(script.contains("_INDEX=''\n"));
+    assert!(script.contains("_BINSTALL_METADATA=true\n"));
+    assert!(script.contains("cargo install --locked --root \"$_PREFIX\""));
+
+    let index = Some("sparse+https://index.example/");
+    let (script, _) = render_cargo_install_script(&query(), &release, false, index).unwrap();
+    assert!(script.contains("_INDEX=$'sparse+https://index.example/'\n"));
+  }
+
+  #[test]
+  fn npm_install_pins_the_resolved_tarball() {
+    let url = Url::parse("https://registry.example/@acme/tool/-/tool-2.1.0.tgz").unwrap();
+    let release = NpmRelease {
+      package: "@acme/tool".to_string(),
+      version: "2.1.0".to_string(),
+      registry_url: "http://localhost:4873".to_string(),
+      tarball: DownloadInfo::from_url(url, APPLICATION_OCTET_STREAM, 0),
+      binaries: vec!["tool".to_string(), "tool-lsp".to_string()],
+    };
+    let (script, extension) = render_npm_install_script(&query(), &release).unwrap();
+    assert_eq!(extension, "sh");
+    assert!(script.contains("_BINARIES=(tool tool-lsp )"));
+    assert!(script.contains("_REGISTRY=$'http://localhost:4873'\n"));
+    assert!(script.contains("npm install --global --prefix \"$_PREFIX\""));
+  }
+}
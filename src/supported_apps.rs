@@ -1,12 +1,22 @@
+use crate::config::{GithubHostConfig, CONFIG};
 use crate::domain::download::Target;
-use crate::domain::platform::TargetDeployment;
+use crate::domain::platform::{TargetArch, TargetDeployment, TargetLibc, TargetOs};
 use crate::error::AppError;
-use crate::providers::gh::get_github_download_links;
+use anyhow::{anyhow, Context};
+use arc_swap::ArcSwap;
+use globset::{Glob, GlobMatcher};
+use log::warn;
 use mime::Mime;
+use regex::Regex;
+use serde::Deserialize;
 use serde_json::json;
 use std::collections::HashMap;
 use std::fmt::Display;
-use std::sync::LazyLock;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::{Arc, LazyLock};
 use url::Url;
 
 macro_rules! safe_int_cast {
@@ -20,111 +30,1039 @@ macro_rules! safe_int_cast {
 }
 
 const GITHUB_API: &str = "https://api.github.com";
+const GITLAB_PROJECTS_API: &str = "/api/v4/projects/";
+const FORGEJO_REPOS_API: &str = "/api/v1/repos/";
+const NPMJS_REGISTRY_HOST: &str = "registry.npmjs.org";
+const OCI_API: &str = "/v2/";
+const EMPTY_FIELD: &str = "-";
+const VERSION_PLACEHOLDER: &str = "VERSION";
+const OS_PLACEHOLDER: &str = "OS";
+const ARCH_PLACEHOLDER: &str = "ARCH";
+const LIBC_PLACEHOLDER: &str = "LIBC";
+/// Platforms assumed for apps whose row leaves `platforms` empty.
+const DEFAULT_PLATFORMS: &str = "linux/amd64;linux/arm64;mac/amd64;mac/arm64;windows/amd64";
 
 pub(crate) fn get_app(name: &str) -> Option<SupportedApp> {
-  SUPPORTED_APPS.get(name).cloned()
+  SUPPORTED_APPS
+    .load()
+    .as_ref()
+    .as_ref()
+    .ok()?
+    .get(name)
+    .cloned()
+}
+
+/// Every registered app, ordered by shortname.
+pub(crate) fn all_apps() -> Vec<SupportedApp> {
+  let mut apps: Vec<SupportedApp> = SUPPORTED_APPS
+    .load()
+    .as_ref()
+    .as_ref()
+    .map(|apps| apps.values().cloned().collect())
+    .unwrap_or_default();
+  apps.sort_by(|a, b| a.shortname.cmp(&b.shortname));
+  apps
+}
+
+/// Forces the registry to load, returning the number of apps or the reason it was rejected.
+pub(crate) fn ensure_loaded() -> anyhow::Result<usize> {
+  match SUPPORTED_APPS.load().as_ref() {
+    Ok(apps) => Ok(apps.len()),
+    Err(message) => Err(anyhow!("{}", message)),
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum AppSource {
+  Github,
+  Url,
+  Pip,
+  Gitlab,
+  Forgejo,
+  Hashicorp,
+  Npm,
+  Oci,
+}
+
+impl FromStr for AppSource {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    match value.to_ascii_lowercase().as_str() {
+      "github" => Ok(AppSource::Github),
+      "url" => Ok(AppSource::Url),
+      "pip" => Ok(AppSource::Pip),
+      "gitlab" => Ok(AppSource::Gitlab),
+      "forgejo" | "gitea" => Ok(AppSource::Forgejo),
+      "hashicorp" => Ok(AppSource::Hashicorp),
+      "npm" => Ok(AppSource::Npm),
+      "oci" => Ok(AppSource::Oci),
+      other => Err(format!(
+        "unknown source '{}', expected one of: github, gitlab, forgejo, hashicorp, url, pip, npm, oci",
+        other
+      )),
+    }
+  }
+}
+
+impl Display for AppSource {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      AppSource::Github => write!(f, "github"),
+      AppSource::Url => write!(f, "url"),
+      AppSource::Pip => write!(f, "pip"),
+      AppSource::Gitlab => write!(f, "gitlab"),
+      AppSource::Forgejo => write!(f, "forgejo"),
+      AppSource::Hashicorp => write!(f, "hashicorp"),
+      AppSource::Npm => write!(f, "npm"),
+      AppSource::Oci => write!(f, "oci"),
+    }
+  }
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct SupportedApp {
   pub(crate) shortname: String,
   pub(crate) repo: Repo,
-  #[allow(dead_code)]
-  pub(crate) source: String,
+  pub(crate) source: AppSource,
+  /// Asset name pattern with `VERSION`, `OS` and `ARCH` placeholders
+  pub(crate) file_pattern: Option<String>,
+  /// Executables to install out of the downloaded archive
+  pub(crate) binaries: Vec<ArchiveBinary>,
+  /// Number of leading directories in every binary's `archive_path` without an `os:` prefix
+  pub(crate) archive_depth: Option<usize>,
+  /// Release tag template, e.g. `jq-VERSION`
+  pub(crate) custom_release_tag: Option<String>,
+  /// Spellings substituted for `OS`/`ARCH` placeholders
+  pub(crate) platform_names: PlatformNames,
+  /// Plain-text endpoint returning the latest version, for sources without a releases API
+  pub(crate) latest_version_url: Option<Url>,
+  /// One-line summary shown in the catalog
+  pub(crate) description: Option<String>,
+  /// os/arch combinations the app publishes builds for
+  pub(crate) platforms: Vec<TargetDeployment>,
 }
 
 impl SupportedApp {
-  pub(crate) fn new(shortname: &str, repo: Repo, source: &str) -> Self {
+  pub(crate) fn new(shortname: &str, repo: Repo, source: AppSource) -> Self {
     Self {
       shortname: shortname.to_string(),
       repo,
-      source: source.to_string(),
-    }
-  }
-}
-
-static SUPPORTED_APPS: LazyLock<HashMap<&str, SupportedApp>> = LazyLock::new(|| {
-  let mut map = HashMap::new();
-  for (app, github_url) in [
-    ("yq", "mikefarah/yq"),
-    ("jq", "jqlang/jq"),
-    ("gh", "cli/cli"),
-    ("jsonnet", "google/go-jsonnet"),
-    ("shellcheck", "koalaman/shellcheck"),
-    ("shfmt", "mvdan/sh"),
-    ("yutc", "adam-huganir/yutc"),
-    ("kubectl", "kubernetes/kubectl"),
-    ("helm", "helm/helm"),
-    ("uv", "astral-sh/uv"),
-  ] {
-    let _ = map.insert(
-      app,
-      SupportedApp::new(app, Repo::github(github_url), "github"),
-    );
+      source,
+      file_pattern: None,
+      binaries: vec![],
+      archive_depth: None,
+      custom_release_tag: None,
+      platform_names: PlatformNames::default(),
+      latest_version_url: None,
+      description: None,
+      platforms: parse_platforms(DEFAULT_PLATFORMS).unwrap_or_default(),
+    }
+  }
+
+  /// Expands the `VERSION`, `OS` and `ARCH` placeholders of a registry pattern.
+  pub(crate) fn render_pattern(
+    &self,
+    pattern: &str,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> String {
+    self.render_escaped_pattern(pattern, version, target_deployment, str::to_string)
+  }
+
+  fn render_escaped_pattern(
+    &self,
+    pattern: &str,
+    version: &str,
+    target_deployment: &TargetDeployment,
+    escape: fn(&str) -> String,
+  ) -> String {
+    pattern
+      .replace(VERSION_PLACEHOLDER, &escape(version))
+      .replace(
+        ARCH_PLACEHOLDER,
+        &escape(&self.platform_names.arch_name(&target_deployment.arch)),
+      )
+      .replace(
+        OS_PLACEHOLDER,
+        &escape(&self.platform_names.os_name(&target_deployment.os)),
+      )
+      .replace(
+        LIBC_PLACEHOLDER,
+        &escape(libc_name(&target_deployment.libc)),
+      )
+  }
+
+  /// The release tag to look up for a user-facing version, using `custom_release_tag` when set.
+  /// Full tags such as `jq-1.7.1` are accepted as well.
+  pub(crate) fn release_tag(&self, version: &str) -> String {
+    match &self.custom_release_tag {
+      Some(template) if version != "latest" => {
+        template.replace(VERSION_PLACEHOLDER, &self.version_from_tag(version))
+      }
+      _ => version.to_string(),
+    }
+  }
+
+  /// The tags a plain version may have been released under, most likely first. Without a
+  /// `custom_release_tag` both `v1.2.0` and `1.2.0` are common.
+  pub(crate) fn release_tags(&self, version: &str) -> Vec<String> {
+    let tag = self.release_tag(version);
+    if self.custom_release_tag.is_some() || version == "latest" || version.starts_with('v') {
+      return vec![tag];
+    }
+    vec![format!("v{}", version), tag]
+  }
+
+  /// The plain version for a release tag, the inverse of [`SupportedApp::release_tag`].
+  pub(crate) fn version_from_tag(&self, tag: &str) -> String {
+    if let Some((prefix, suffix)) = self
+      .custom_release_tag
+      .as_deref()
+      .and_then(|template| template.split_once(VERSION_PLACEHOLDER))
+    {
+      if let Some(version) = tag
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_suffix(suffix))
+      {
+        return version.to_string();
+      }
+    }
+    tag.trim_start_matches('v').to_string()
+  }
+
+  /// The binaries with their archive paths expanded. `*` is kept, since some archives nest
+  /// the binaries under a directory named after the full asset.
+  /// Binaries with an `os:` prefix replace the others in that os's archives.
+  pub(crate) fn archive_binaries(
+    &self,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> Vec<ArchiveBinary> {
+    let os = Some(&target_deployment.os);
+    let os_specific = self.binaries.iter().any(|binary| binary.os.as_ref() == os);
+    self
+      .binaries
+      .iter()
+      .filter(|binary| match os_specific {
+        true => binary.os.as_ref() == os,
+        false => binary.os.is_none(),
+      })
+      .map(|binary| ArchiveBinary {
+        archive_path: self.render_pattern(&binary.archive_path, version, target_deployment),
+        install_name: binary.install_name.clone(),
+        os: binary.os.clone(),
+      })
+      .collect()
+  }
+
+  /// Leading directories to strip from the binaries' paths in `os`'s archives: those of its own
+  /// `os:` paths when it has any, `archive_depth` otherwise.
+  pub(crate) fn archive_depth_for(&self, os: &TargetOs) -> Option<usize> {
+    match self
+      .binaries
+      .iter()
+      .find(|binary| binary.os.as_ref() == Some(os))
+    {
+      Some(binary) => Some(binary.archive_path.matches('/').count()),
+      None => self.archive_depth,
+    }
+  }
+
+  /// Compiles `file_pattern` for one version and target. A leading `^` selects regex syntax,
+  /// anything else is a glob matched against the whole asset name.
+  pub(crate) fn asset_pattern(
+    &self,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> Result<Option<AssetPattern>, String> {
+    let Some(pattern) = &self.file_pattern else {
+      return Ok(None);
+    };
+    if pattern.starts_with('^') {
+      let rendered =
+        self.render_escaped_pattern(pattern, version, target_deployment, regex::escape);
+      Regex::new(&rendered)
+        .map(|regex| Some(AssetPattern::Regex(regex)))
+        .map_err(|err| format!("file_pattern '{}' is not a valid regex: {}", pattern, err))
+    } else {
+      let rendered =
+        self.render_escaped_pattern(pattern, version, target_deployment, globset::escape);
+      Glob::new(&rendered)
+        .map(|glob| Some(AssetPattern::Glob(glob.compile_matcher())))
+        .map_err(|err| format!("file_pattern '{}' is not a valid glob: {}", pattern, err))
+    }
+  }
+
+  /// Release assets matching `file_pattern`, ordered by name. Apps without a pattern get every
+  /// asset back, for the caller to choose from.
+  pub(crate) fn match_assets(
+    &self,
+    assets: Vec<DownloadInfo>,
+    version: &str,
+    target_deployment: &TargetDeployment,
+  ) -> Result<Vec<DownloadInfo>, AppError> {
+    let Some(pattern) = self
+      .asset_pattern(version, target_deployment)
+      .map_err(AppError::InvalidInput)?
+    else {
+      return Ok(assets);
+    };
+    let (mut matched, mut rest): (Vec<DownloadInfo>, Vec<DownloadInfo>) = assets
+      .into_iter()
+      .partition(|asset| pattern.is_match(&asset.name));
+    // without a native build, look for one the target runs through emulation
+    for arch in target_deployment.fallback_archs() {
+      if !matched.is_empty() {
+        break;
+      }
+      let emulated = TargetDeployment {
+        arch: arch.clone(),
+        ..target_deployment.clone()
+      };
+      let Some(pattern) = self
+        .asset_pattern(version, &emulated)
+        .map_err(AppError::InvalidInput)?
+      else {
+        break;
+      };
+      let (emulated_assets, others): (Vec<DownloadInfo>, Vec<DownloadInfo>) = rest
+        .into_iter()
+        .partition(|asset| pattern.is_match(&asset.name));
+      rest = others;
+      matched = emulated_assets
+        .into_iter()
+        .map(|asset| DownloadInfo {
+          fallback: Some(arch.clone()),
+          ..asset
+        })
+        .collect();
+    }
+    matched.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(matched)
+  }
+
+  /// Narrows the assets from `match_assets` down to the single one to install, warning when
+  /// `file_pattern` was ambiguous. Without a pattern the choice is left to the caller.
+  pub(crate) fn pick_asset(
+    &self,
+    mut matched: Vec<DownloadInfo>,
+    target_deployment: &TargetDeployment,
+  ) -> Vec<DownloadInfo> {
+    if self.file_pattern.is_none() {
+      return matched;
+    }
+    if matched.len() > 1 {
+      warn!(
+        "file_pattern for {} matched {} assets for {}, using {}",
+        self.shortname,
+        matched.len(),
+        target_deployment,
+        matched[0].name
+      );
+    }
+    matched.truncate(1);
+    matched
+  }
+}
+
+/// What `LIBC` expands to. Requests that leave the libc to the host or ask for none get the musl
+/// build, which is usually static and runs on glibc hosts as well.
+fn libc_name(libc: &Option<TargetLibc>) -> &'static str {
+  match libc {
+    Some(TargetLibc::Gnu) => "gnu",
+    _ => "musl",
+  }
+}
+
+/// An executable inside a release archive and the name it is installed under.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ArchiveBinary {
+  pub(crate) archive_path: String,
+  pub(crate) install_name: String,
+  /// The only os whose archives hold the binary at this path
+  pub(crate) os: Option<TargetOs>,
+}
+
+impl FromStr for ArchiveBinary {
+  type Err = String;
+
+  /// Parses `path` or `path=name`, where the name defaults to the path's file name, optionally
+  /// prefixed with `os:` for archives laid out differently on one os.
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let (os, path) = match value.split_once(':') {
+      Some((os, path)) => match TargetOs::from(os.trim()) {
+        TargetOs::Unknown => return Err(format!("unknown os '{}' in archive_path", os)),
+        os => (Some(os), path.trim()),
+      },
+      None => (None, value),
+    };
+    let (archive_path, install_name) = match path.split_once('=') {
+      Some((path, name)) => (path.trim(), name.trim()),
+      None => (path, path.rsplit('/').next().unwrap_or(path)),
+    };
+    if archive_path.is_empty() || archive_path.ends_with('/') {
+      return Err(format!("archive_path '{}' must name a file", value));
+    }
+    if install_name.is_empty() || install_name.contains(['/', '\\']) {
+      return Err(format!(
+        "install name '{}' must be a plain file name",
+        install_name
+      ));
+    }
+    Ok(ArchiveBinary {
+      archive_path: archive_path.to_string(),
+      install_name: install_name.to_string(),
+      os,
+    })
+  }
+}
+
+/// A `file_pattern` with its placeholders expanded, ready to match asset names.
+#[derive(Debug)]
+pub(crate) enum AssetPattern {
+  Glob(GlobMatcher),
+  Regex(Regex),
+}
+
+impl AssetPattern {
+  pub(crate) fn is_match(&self, name: &str) -> bool {
+    match self {
+      AssetPattern::Glob(matcher) => matcher.is_match(name),
+      AssetPattern::Regex(regex) => regex.is_match(name),
+    }
+  }
+}
+
+/// Per-app overrides of the Go-style `OS`/`ARCH` spellings, e.g. `mac=macos;amd64=x86_64`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct PlatformNames {
+  os: Vec<(TargetOs, String)>,
+  arch: Vec<(TargetArch, String)>,
+}
+
+impl PlatformNames {
+  pub(crate) fn os_name(&self, os: &TargetOs) -> String {
+    self
+      .os
+      .iter()
+      .find(|(known, _)| known == os)
+      .map(|(_, name)| name.clone())
+      .unwrap_or_else(|| os.release_name().to_string())
+  }
+
+  pub(crate) fn arch_name(&self, arch: &TargetArch) -> String {
+    self
+      .arch
+      .iter()
+      .find(|(known, _)| known == arch)
+      .map(|(_, name)| name.clone())
+      .unwrap_or_else(|| arch.release_name().to_string())
+  }
+}
+
+impl FromStr for PlatformNames {
+  type Err = String;
+
+  fn from_str(value: &str) -> Result<Self, Self::Err> {
+    let mut names = PlatformNames::default();
+    for pair in value.split(';').map(str::trim).filter(|p| !p.is_empty()) {
+      let (key, name) = pair
+        .split_once('=')
+        .filter(|(key, name)| !key.trim().is_empty() && !name.trim().is_empty())
+        .ok_or_else(|| format!("platform name '{}' must be in key=name form", pair))?;
+      let (key, name) = (key.trim(), name.trim().to_string());
+      let os = TargetOs::identify(key);
+      let arch = TargetArch::identify(key);
+      if os != TargetOs::Unknown {
+        names.os.push((os, name));
+      } else if arch != TargetArch::Unknown {
+        // arm64 and aarch64 are the same platform, whichever spelling the row used
+        if matches!(arch, TargetArch::Arm64 | TargetArch::Aarch64) {
+          names.arch.push((TargetArch::Arm64, name.clone()));
+          names.arch.push((TargetArch::Aarch64, name));
+        } else {
+          names.arch.push((arch, name));
+        }
+      } else {
+        return Err(format!("unknown os or arch '{}' in platform_names", key));
+      }
+    }
+    Ok(names)
+  }
+}
+
+/// A raw row of the registry CSV, before validation.
+#[derive(Debug, Deserialize)]
+struct AppRecord {
+  shortname: String,
+  repo: String,
+  source: String,
+  file_pattern: String,
+  archive_path: String,
+  archive_depth: String,
+  custom_release_tag: String,
+  #[serde(default)]
+  platform_names: String,
+  #[serde(default)]
+  latest_version_url: String,
+  #[serde(default)]
+  description: String,
+  #[serde(default)]
+  platforms: String,
+}
+
+impl AppRecord {
+  fn into_app(self) -> Result<SupportedApp, String> {
+    let shortname = self.shortname;
+    if shortname.is_empty() || shortname == EMPTY_FIELD {
+      return Err("shortname cannot be empty".to_string());
+    }
+    if !shortname
+      .chars()
+      .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+    {
+      return Err(format!(
+        "shortname '{}' contains invalid characters",
+        shortname
+      ));
+    }
+
+    let source = AppSource::from_str(&self.source)?;
+    let repo_field = optional_field(self.repo);
+    let repo = match source {
+      AppSource::Github => {
+        let repo = repo_field.ok_or("github source requires a repo")?;
+        match repo.split('/').collect::<Vec<_>>()[..] {
+          [owner, name] if !owner.is_empty() && !name.is_empty() => Repo::github(&repo),
+          [host, owner, name] if !owner.is_empty() && !name.is_empty() => {
+            let config = CONFIG.load();
+            let host = config.github.host(host).ok_or_else(|| {
+              format!(
+                "github host '{}' is not configured under github.hosts",
+                host
+              )
+            })?;
+            Repo::github_enterprise(host, &format!("{}/{}", owner, name))
+          }
+          _ => {
+            return Err(format!(
+              "github repo '{}' must be in owner/name or host/owner/name form",
+              repo
+            ))
+          }
+        }
+      }
+      AppSource::Url => {
+        let host = repo_field.ok_or("url source requires a repo host")?;
+        let repo = Repo::url(&format!("https://{}", host.trim_end_matches('/')));
+        repo
+          .get_url()
+          .map_err(|_| format!("url repo '{}' is not a valid host", host))?;
+        repo
+      }
+      AppSource::Pip => Repo::python(repo_field.as_deref().unwrap_or(&shortname)),
+      AppSource::Npm => {
+        let package = repo_field.unwrap_or_else(|| shortname.clone());
+        let valid = match package.strip_prefix('@') {
+          Some(scoped) => {
+            matches!(scoped.split_once('/'), Some((scope, name)) if !scope.is_empty() && !name.is_empty() && !name.contains('/'))
+          }
+          None => !package.contains('/'),
+        };
+        if !valid {
+          return Err(format!(
+            "npm repo '{}' must be a package name such as prettier or @scope/name",
+            package
+          ));
+        }
+        Repo::npm(&package)
+      }
+      AppSource::Oci => {
+        let reference = repo_field.ok_or("oci source requires a repo")?;
+        match reference.split_once('/') {
+          Some((host, name))
+            if (host.contains(['.', ':']) || host == "localhost")
+              && !name.is_empty()
+              && !name.split('/').any(str::is_empty) =>
+          {
+            Repo::oci(host, name)
+          }
+          _ => {
+            return Err(format!(
+              "oci repo '{}' must be in host/repository form, e.g. ghcr.io/owner/name",
+              reference
+            ))
+          }
+        }
+      }
+      AppSource::Hashicorp => {
+        let product = repo_field.unwrap_or_else(|| shortname.clone());
+        if product.contains('/') {
+          return Err(format!(
+            "hashicorp repo '{}' must be a product name such as terraform",
+            product
+          ));
+        }
+        Repo::hashicorp(&product)
+      }
+      AppSource::Gitlab => {
+        let project = repo_field.ok_or("gitlab source requires a repo")?;
+        if project.split('/').count() < 2 || project.split('/').any(str::is_empty) {
+          return Err(format!(
+            "gitlab repo '{}' must be in group/project form",
+            project
+          ));
+        }
+        Repo::gitlab(&project)
+      }
+      AppSource::Forgejo => {
+        let repo = repo_field.ok_or("forgejo source requires a repo")?;
+        match repo.splitn(3, '/').collect::<Vec<_>>()[..] {
+          [host, owner, name]
+            if !host.is_empty() && !owner.is_empty() && !name.is_empty() && !name.contains('/') =>
+          {
+            Repo::forgejo(host, owner, name)
+          }
+          _ => {
+            return Err(format!(
+              "forgejo repo '{}' must be in host/owner/name form",
+              repo
+            ))
+          }
+        }
+      }
+    };
+
+    let binaries = optional_field(self.archive_path)
+      .map(|paths| {
+        paths
+          .split(';')
+          .map(str::trim)
+          .filter(|path| !path.is_empty())
+          .map(ArchiveBinary::from_str)
+          .collect::<Result<Vec<_>, _>>()
+      })
+      .transpose()?
+      .unwrap_or_default();
+    let archive_depth = optional_field(self.archive_depth)
+      .map(|depth| {
+        depth
+          .parse::<usize>()
+          .map_err(|_| format!("archive_depth '{}' is not a non-negative integer", depth))
+      })
+      .transpose()?;
+    match (binaries.is_empty(), archive_depth) {
+      (false, Some(depth)) => {
+        // tarballs are unpacked with a single --strip-components, so every path of an os
+        // shares the depth, which is taken from the paths themselves for `os:` ones
+        for binary in &binaries {
+          let actual = binary.archive_path.matches('/').count();
+          let expected = match &binary.os {
+            Some(os) => binaries
+              .iter()
+              .find(|other| other.os.as_ref() == Some(os))
+              .map_or(actual, |first| first.archive_path.matches('/').count()),
+            None => depth,
+          };
+          if actual != expected {
+            return Err(format!(
+              "archive_depth {} does not match archive_path '{}' ({} directories)",
+              expected, binary.archive_path, actual
+            ));
+          }
+        }
+      }
+      (false, None) => return Err("archive_path requires an archive_depth".to_string()),
+      (true, Some(_)) => return Err("archive_depth requires an archive_path".to_string()),
+      (true, None) => {}
+    }
+
+    let custom_release_tag = optional_field(self.custom_release_tag);
+    if let Some(tag) = &custom_release_tag {
+      if !tag.contains(VERSION_PLACEHOLDER) {
+        return Err(format!(
+          "custom_release_tag '{}' must contain the {} placeholder",
+          tag, VERSION_PLACEHOLDER
+        ));
+      }
+    }
+
+    let file_pattern = optional_field(self.file_pattern);
+    if source == AppSource::Url && file_pattern.is_none() {
+      return Err("url source requires a file_pattern".to_string());
+    }
+
+    let platform_names = optional_field(self.platform_names)
+      .map(|names| PlatformNames::from_str(&names))
+      .transpose()?
+      .unwrap_or_default();
+    let latest_version_url = optional_field(self.latest_version_url)
+      .map(|url| {
+        Url::parse(&url).map_err(|err| format!("latest_version_url '{}' is invalid: {}", url, err))
+      })
+      .transpose()?;
+
+    let platforms = parse_platforms(
+      optional_field(self.platforms)
+        .as_deref()
+        .unwrap_or(DEFAULT_PLATFORMS),
+    )?;
+
+    let app = SupportedApp {
+      shortname,
+      repo,
+      source,
+      file_pattern,
+      binaries,
+      archive_depth,
+      custom_release_tag,
+      platform_names,
+      latest_version_url,
+      description: optional_field(self.description),
+      platforms,
+    };
+    // url patterns are paths rather than globs, everything else must compile
+    if app.source != AppSource::Url {
+      app.asset_pattern(
+        "0.0.0",
+        &TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64),
+      )?;
+    }
+    Ok(app)
+  }
+}
+
+/// PEP 503 name normalization, as used in simple index URLs.
+fn normalize_python_package(package: &str) -> String {
+  let mut normalized = String::with_capacity(package.len());
+  for c in package.chars() {
+    if matches!(c, '-' | '_' | '.') {
+      if !normalized.ends_with('-') {
+        normalized.push('-');
+      }
+    } else {
+      normalized.push(c.to_ascii_lowercase());
+    }
+  }
+  normalized
+}
+
+/// Parses `os/arch` pairs separated by `;`, e.g. `linux/amd64;mac/arm64`.
+fn parse_platforms(value: &str) -> Result<Vec<TargetDeployment>, String> {
+  value
+    .split(';')
+    .map(str::trim)
+    .filter(|pair| !pair.is_empty())
+    .map(|pair| {
+      let (os, arch) = pair
+        .split_once('/')
+        .ok_or_else(|| format!("platform '{}' must be in os/arch form", pair))?;
+      let deployment = TargetDeployment::new(TargetOs::identify(os), TargetArch::identify(arch));
+      if deployment.os == TargetOs::Unknown || deployment.arch == TargetArch::Unknown {
+        return Err(format!("unknown os or arch in platform '{}'", pair));
+      }
+      Ok(deployment)
+    })
+    .collect()
+}
+
+fn optional_field(value: String) -> Option<String> {
+  if value.is_empty() || value == EMPTY_FIELD {
+    None
+  } else {
+    Some(value)
   }
-  map
-});
+}
+
+/// Parses a registry CSV, failing on the first invalid row.
+pub(crate) fn parse_registry<R: Read>(
+  reader: R,
+  origin: &str,
+) -> anyhow::Result<HashMap<String, SupportedApp>> {
+  // flexible so that trailing optional columns may be left off
+  let mut csv_reader = csv::ReaderBuilder::new()
+    .trim(csv::Trim::All)
+    .flexible(true)
+    .from_reader(reader);
+  let headers = csv_reader
+    .headers()
+    .with_context(|| format!("{}:1: malformed header", origin))?
+    .clone();
+  let mut apps = HashMap::new();
+  for (idx, record) in csv_reader.records().enumerate() {
+    // header is line 1
+    let line = idx + 2;
+    let mut record = record.with_context(|| format!("{}:{}: malformed row", origin, line))?;
+    while record.len() < headers.len() {
+      record.push_field("");
+    }
+    let record: AppRecord = record
+      .deserialize(Some(&headers))
+      .with_context(|| format!("{}:{}: malformed row", origin, line))?;
+    let app = record
+      .into_app()
+      .map_err(|err| anyhow!("{}:{}: {}", origin, line, err))?;
+    if apps.contains_key(&app.shortname) {
+      return Err(anyhow!(
+        "{}:{}: duplicate shortname '{}'",
+        origin,
+        line,
+        app.shortname
+      ));
+    }
+    apps.insert(app.shortname.clone(), app);
+  }
+  Ok(apps)
+}
+
+pub(crate) fn load_registry<P: AsRef<Path>>(
+  path: P,
+) -> anyhow::Result<HashMap<String, SupportedApp>> {
+  let path = path.as_ref();
+  let file =
+    File::open(path).with_context(|| format!("Failed to read app registry: {}", path.display()))?;
+  parse_registry(file, &path.display().to_string())
+}
+
+/// Replaces the served registry with the one at `path`, returning the number of apps. A
+/// registry that fails to load or validate leaves the current one in place.
+pub(crate) fn reload_registry<P: AsRef<Path>>(path: P) -> anyhow::Result<usize> {
+  let apps = load_registry(path)?;
+  let count = apps.len();
+  SUPPORTED_APPS.store(Arc::new(Ok(apps)));
+  Ok(count)
+}
+
+static SUPPORTED_APPS: LazyLock<ArcSwap<Result<HashMap<String, SupportedApp>, String>>> =
+  LazyLock::new(|| {
+    ArcSwap::from_pointee(load_registry(&CONFIG.load().registry.path).map_err(|err| {
+      log::error!("failed to load app registry: {:#}", err);
+      format!("{:#}", err)
+    }))
+  });
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
 pub(crate) enum Repo {
-  Github(String),
-  #[allow(dead_code)]
+  /// A repo's API URL, with the name of the configured host serving it; github.com has none
+  Github {
+    host: Option<String>,
+    url: String,
+  },
   Url(String),
-  #[allow(dead_code)]
   Python(String),
+  Gitlab(String),
+  Forgejo(String),
+  Hashicorp(String),
+  Npm(String),
+  Oci(String),
 }
 
 impl Repo {
   pub(crate) fn github(repo: &str) -> Self {
-    Self::Github(format!("{}/repos/{}", GITHUB_API, repo))
+    Self::Github {
+      host: None,
+      url: format!("{}/repos/{}", GITHUB_API, repo),
+    }
+  }
+
+  /// A repo on a configured GitHub Enterprise Server host.
+  pub(crate) fn github_enterprise(host: &GithubHostConfig, repo: &str) -> Self {
+    Self::Github {
+      host: Some(host.name.clone()),
+      url: format!("{}/repos/{}", host.api_url.trim_end_matches('/'), repo),
+    }
+  }
+
+  /// The configured GitHub Enterprise Server host serving this repo, `None` for github.com.
+  /// Repos whose host is gone from the config are an error, never github.com.
+  pub(crate) fn github_host(&self) -> Result<Option<GithubHostConfig>, AppError> {
+    match self {
+      Repo::Github {
+        host: Some(name), ..
+      } => CONFIG
+        .load()
+        .github
+        .host(name)
+        .cloned()
+        .map(Some)
+        .ok_or_else(|| AppError::HostNotAllowed(name.clone())),
+      Repo::Github { host: None, url } if !url.starts_with(&format!("{}/", GITHUB_API)) => Err(
+        AppError::InvalidInput(format!("No GitHub host is configured for {}", url)),
+      ),
+      _ => Ok(None),
+    }
+  }
+
+  /// A project on the configured GitLab instance; `project` is its full path, nested groups
+  /// included.
+  pub(crate) fn gitlab(project: &str) -> Self {
+    let encoded: String = url::form_urlencoded::byte_serialize(project.as_bytes()).collect();
+    Self::Gitlab(format!(
+      "{}{}{}",
+      CONFIG.load().gitlab.base_url.trim_end_matches('/'),
+      GITLAB_PROJECTS_API,
+      encoded
+    ))
+  }
+
+  /// A repository on a Gitea or Forgejo instance, such as Codeberg.
+  pub(crate) fn forgejo(host: &str, owner: &str, repo: &str) -> Self {
+    Self::Forgejo(format!(
+      "https://{}{}{}/{}",
+      host, FORGEJO_REPOS_API, owner, repo
+    ))
+  }
+
+  /// A product on the HashiCorp releases site, e.g. `terraform`.
+  pub(crate) fn hashicorp(product: &str) -> Self {
+    Self::Hashicorp(format!(
+      "{}/{}/",
+      CONFIG.load().hashicorp.base_url.trim_end_matches('/'),
+      product
+    ))
+  }
+
+  /// A package on the configured npm registry; scoped names keep their `@scope/` prefix.
+  pub(crate) fn npm(package: &str) -> Self {
+    Self::Npm(format!(
+      "{}/{}",
+      CONFIG.load().npm.registry_url.trim_end_matches('/'),
+      package.replacen('/', "%2F", 1)
+    ))
+  }
+
+  /// A repository on an OCI registry. Docker Hub is reached through its registry host, and
+  /// loopback or configured insecure registries over plain http.
+  pub(crate) fn oci(host: &str, name: &str) -> Self {
+    let host = match host {
+      "docker.io" => "registry-1.docker.io",
+      host => host,
+    };
+    let loopback = ["localhost", "127.0.0.1"]
+      .iter()
+      .any(|loopback| host.split(':').next() == Some(*loopback));
+    let scheme = match loopback
+      || CONFIG
+        .load()
+        .oci
+        .insecure_registries
+        .iter()
+        .any(|insecure| insecure == host)
+    {
+      true => "http",
+      false => "https",
+    };
+    Self::Oci(format!("{}://{}{}{}/", scheme, host, OCI_API, name))
   }
 
-  #[allow(dead_code)]
   fn url(url: &str) -> Self {
     Self::Url(url.to_string())
   }
 
-  #[allow(dead_code)]
-  fn python(app: &str) -> Self {
-    Self::Python(format!("https://pypi.org/simple/{}", app))
+  fn python(package: &str) -> Self {
+    Self::Python(format!(
+      "{}/{}/",
+      CONFIG.load().python.index_url.trim_end_matches('/'),
+      normalize_python_package(package)
+    ))
   }
 
-  fn get_url(&self) -> Result<Url, AppError> {
+  pub(crate) fn get_url(&self) -> Result<Url, AppError> {
     let parsed = match self {
-      Repo::Github(repo) => Url::parse(repo),
+      Repo::Github { url, .. } => Url::parse(url),
       Repo::Url(url) => Url::parse(url),
       Repo::Python(url) => Url::parse(url),
+      Repo::Gitlab(url) => Url::parse(url),
+      Repo::Forgejo(url) => Url::parse(url),
+      Repo::Hashicorp(url) => Url::parse(url),
+      Repo::Npm(url) => Url::parse(url),
+      Repo::Oci(url) => Url::parse(url),
     };
     parsed.map_err(|err| AppError::InvalidInput(format!("Invalid repo URL: {}", err)))
   }
 
-  pub(crate) fn get_github_repo(&self) -> Result<String, AppError> {
-    Ok(
-      self
-        .get_url()?
-        .path()
-        .trim_start_matches("/repos/")
+  /// Normalized package name, the last segment of the simple index project URL.
+  pub(crate) fn get_python_package(&self) -> Result<String, AppError> {
+    self
+      .get_url()?
+      .path_segments()
+      .and_then(|mut segments| segments.rfind(|s| !s.is_empty()))
+      .map(str::to_string)
+      .ok_or_else(|| AppError::InvalidInput("Invalid python index URL".to_string()))
+  }
+
+  /// Package name of an npm repo, `@scope/name` for scoped packages.
+  pub(crate) fn get_npm_package(&self) -> Result<String, AppError> {
+    let Repo::Npm(url) = self else {
+      return Err(AppError::InvalidInput(format!(
+        "Not an npm package: {:?}",
+        self
+      )));
+    };
+    url
+      .rsplit_once('/')
+      .map(|(_, package)| package.replacen("%2F", "/", 1))
+      .ok_or_else(|| AppError::InvalidInput(format!("Invalid npm package URL: {}", url)))
+  }
+
+  /// Human-facing page for the repo: the project page, the download host or the index page.
+  pub(crate) fn html_url(&self) -> Result<Url, AppError> {
+    let page = match self {
+      Repo::Github { .. } => match self.github_host()? {
+        Some(host) => format!(
+          "{}/{}",
+          host.web_url.trim_end_matches('/'),
+          self.get_github_repo()?
+        ),
+        None => format!("https://github.com/{}", self.get_github_repo()?),
+      },
+      Repo::Gitlab(url) => {
+        let (base, _) = url
+          .split_once(GITLAB_PROJECTS_API)
+          .ok_or_else(|| AppError::InvalidInput(format!("Invalid GitLab API URL: {}", url)))?;
+        format!("{}/{}", base, self.get_gitlab_project()?)
+      }
+      Repo::Forgejo(url) => url.replacen(FORGEJO_REPOS_API, "/", 1),
+      Repo::Oci(url) => url
+        .replacen(OCI_API, "/", 1)
+        .trim_end_matches('/')
         .to_string(),
+      Repo::Npm(_) if self.get_url()?.host_str() == Some(NPMJS_REGISTRY_HOST) => {
+        format!("https://www.npmjs.com/package/{}", self.get_npm_package()?)
+      }
+      Repo::Url(_) | Repo::Python(_) | Repo::Hashicorp(_) | Repo::Npm(_) => return self.get_url(),
+    };
+    Url::parse(&page).map_err(|err| AppError::InvalidInput(format!("Invalid repo URL: {}", err)))
+  }
+
+  /// Full `group/subgroup/project` path of a GitLab project.
+  pub(crate) fn get_gitlab_project(&self) -> Result<String, AppError> {
+    let Repo::Gitlab(url) = self else {
+      return Err(AppError::InvalidInput(format!(
+        "Not a GitLab project: {:?}",
+        self
+      )));
+    };
+    let (_, encoded) = url
+      .split_once(GITLAB_PROJECTS_API)
+      .ok_or_else(|| AppError::InvalidInput(format!("Invalid GitLab API URL: {}", url)))?;
+    Ok(
+      url::form_urlencoded::parse(format!("project={}", encoded).as_bytes())
+        .map(|(_, project)| project.into_owned())
+        .next()
+        .unwrap_or_default(),
     )
   }
 
-  #[allow(dead_code)]
-  pub(crate) async fn get_download_link(
-    &self,
-    version: &str,
-    target_deployment: &TargetDeployment,
-  ) -> Result<Vec<DownloadInfo>, AppError> {
-    match self {
-      Repo::Github(_) => get_github_download_links(self, target_deployment, version).await,
-      Repo::Url(url) => Err(AppError::InvalidInput(format!(
-        "{} is not a github repo",
-        url
-      ))),
-      Repo::Python(url) => Err(AppError::InvalidInput(format!(
-        "{} is not a github repo",
-        url
-      ))),
-    }
+  /// `owner/name` of a GitHub repo, on whichever host it lives.
+  pub(crate) fn get_github_repo(&self) -> Result<String, AppError> {
+    let url = self.get_url()?;
+    url
+      .path()
+      .split_once("/repos/")
+      .map(|(_, repo)| repo.to_string())
+      .ok_or_else(|| AppError::InvalidInput(format!("Invalid github repo URL: {}", url)))
   }
 }
 
@@ -136,6 +1074,14 @@ pub(crate) struct DownloadInfo {
   pub(crate) content_type: Mime,
   pub(crate) size: u64,
   pub(crate) target: Target,
+  /// Plain version of the release the file belongs to, once resolved
+  pub(crate) version: Option<String>,
+  /// Hex SHA-256 digest published alongside the file, when the source has one
+  pub(crate) sha256: Option<String>,
+  /// Rank among the release's candidates for the requested target, higher is better
+  pub(crate) score: Option<i64>,
+  /// The arch the file is built for when the requested target only runs it through emulation
+  pub(crate) fallback: Option<TargetArch>,
 }
 
 impl DownloadInfo {
@@ -154,6 +1100,36 @@ impl DownloadInfo {
       content_type: mime.to_owned(),
       size,
       target: Target::identify(&asset.name, Some(&mime)),
+      version: None,
+      sha256: None,
+      score: None,
+      fallback: None,
+    }
+  }
+
+  /// Describes a file served from a plain URL, where the name is the last path segment.
+  pub(crate) fn from_url(url: Url, content_type: Mime, size: u64) -> Self {
+    let name = url
+      .path_segments()
+      .and_then(|mut segments| segments.next_back())
+      .unwrap_or_default()
+      .to_string();
+    Self::from_named_url(name, url, content_type, size)
+  }
+
+  /// Describes a file whose name is known separately from its download URL.
+  pub(crate) fn from_named_url(name: String, url: Url, content_type: Mime, size: u64) -> Self {
+    Self {
+      target: Target::identify(&name, Some(&content_type)),
+      name,
+      label: "".to_string(),
+      url,
+      content_type,
+      size,
+      version: None,
+      sha256: None,
+      score: None,
+      fallback: None,
     }
   }
 
@@ -166,7 +1142,12 @@ impl DownloadInfo {
         "filetype": self.target.filetype.to_string(),
         "os": self.target.deployment.os.to_string(),
         "arch": self.target.deployment.arch.to_string(),
-        "size": self.size
+        "libc": self.target.deployment.libc.clone().unwrap_or_default().to_string(),
+        "size": self.size,
+        "version": self.version,
+        "sha256": self.sha256,
+        "score": self.score,
+        "fallback": self.fallback.as_ref().map(ToString::to_string)
     })
   }
 }
@@ -180,3 +1161,425 @@ impl Display for DownloadInfo {
     )
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const HEADER: &str =
+    "shortname,repo,source,file_pattern,archive_path,archive_depth,custom_release_tag,platform_names,latest_version_url,description,platforms\n";
+
+  fn parse(rows: &str) -> anyhow::Result<HashMap<String, SupportedApp>> {
+    parse_registry(format!("{}{}", HEADER, rows).as_bytes(), "test.csv")
+  }
+
+  #[test]
+  fn bundled_registry_is_valid() {
+    let apps = load_registry("apps.csv").expect("apps.csv should be valid");
+    let jq = apps.get("jq").expect("jq should be registered");
+    assert_eq!(jq.source, AppSource::Github);
+    assert_eq!(jq.custom_release_tag.as_deref(), Some("jq-VERSION"));
+    let gh = apps.get("gh").expect("gh should be registered");
+    assert_eq!(gh.archive_depth, Some(2));
+    let helm = apps.get("helm").expect("helm should be registered");
+    assert_eq!(helm.source, AppSource::Url);
+    assert!(helm.latest_version_url.is_some());
+    assert!(apps.values().all(|app| app.description.is_some()));
+    assert!(!apps["shellcheck"]
+      .platforms
+      .contains(&TargetDeployment::new(TargetOs::Windows, TargetArch::Amd64)));
+    assert_eq!(apps["yq"].platforms.len(), 5);
+  }
+
+  #[test]
+  fn failed_reload_keeps_the_current_registry() {
+    let before = all_apps().len();
+    assert!(before > 0);
+
+    let broken = std::env::temp_dir().join(format!("termlibs-broken-{}.csv", std::process::id()));
+    std::fs::write(&broken, format!("{}yq,mikefarah/yq,svn,-,-,-,-\n", HEADER)).unwrap();
+    let err = reload_registry(&broken).unwrap_err();
+    std::fs::remove_file(&broken).unwrap();
+    assert!(format!("{:#}", err).contains("unknown source 'svn'"));
+    assert!(reload_registry("missing-registry.csv").is_err());
+
+    assert_eq!(all_apps().len(), before);
+    assert!(get_app("yq").is_some());
+    assert_eq!(reload_registry("apps.csv").unwrap(), before);
+  }
+
+  #[test]
+  fn dash_and_empty_fields_are_none() {
+    let apps = parse("yq,mikefarah/yq,github,,-,-,-\n").unwrap();
+    let yq = apps.get("yq").unwrap();
+    assert_eq!(yq.file_pattern, None);
+    assert!(yq.binaries.is_empty());
+    assert_eq!(yq.custom_release_tag, None);
+    assert_eq!(yq.repo, Repo::github("mikefarah/yq"));
+  }
+
+  #[test]
+  fn rejects_invalid_rows() {
+    for (row, expected) in [
+      ("yq,mikefarah/yq,svn,-,-,-,-", "unknown source 'svn'"),
+      ("yq,yq,github,-,-,-,-", "owner/name or host/owner/name form"),
+      (
+        "tool,ghe/owner/tool,github,-,-,-,-",
+        "'ghe' is not configured",
+      ),
+      ("tool,tool,gitlab,-,-,-,-", "group/project form"),
+      (
+        "tool,codeberg.org/tool,forgejo,-,-,-,-",
+        "host/owner/name form",
+      ),
+      (
+        "gh,cli/cli,github,-,gh_*/bin/gh,1,-",
+        "does not match archive_path",
+      ),
+      (
+        "gh,cli/cli,github,-,gh_*/bin/gh,two,-",
+        "not a non-negative integer",
+      ),
+      (
+        "uv,astral-sh/uv,github,-,uv/uv;sunos:uv,1,-",
+        "unknown os 'sunos'",
+      ),
+      (
+        "uv,astral-sh/uv,github,-,uv/uv;windows:uv;windows:bin/uvx,1,-",
+        "does not match archive_path 'bin/uvx'",
+      ),
+      ("gh,cli/cli,github,-,gh,-,-", "requires an archive_depth"),
+      (
+        "uv,astral-sh/uv,github,-,uv-dir/uv;uvx,1,-",
+        "does not match archive_path 'uvx'",
+      ),
+      (
+        "uv,astral-sh/uv,github,-,uv-dir/uv=bin/uv,1,-",
+        "must be a plain file name",
+      ),
+      ("uv,astral-sh/uv,github,-,uv-dir/,1,-", "must name a file"),
+      ("jq,jqlang/jq,github,-,-,-,jq-latest", "VERSION placeholder"),
+      ("helm,get.helm.sh,url,-,-,-,-", "requires a file_pattern"),
+      (
+        "tool,example.com,url,tool-OS,-,-,-,plan9=p9",
+        "unknown os or arch 'plan9'",
+      ),
+      ("tool,example.com,url,tool-OS,-,-,-,mac", "key=name form"),
+      (
+        "yq,mikefarah/yq,github,-,-,-,-,-,-,-,linux",
+        "must be in os/arch form",
+      ),
+      (
+        "yq,mikefarah/yq,github,-,-,-,-,-,-,-,linux/sparc",
+        "unknown os or arch",
+      ),
+      ("tool,acme/tool,npm,-,-,-,-", "@scope/name"),
+      ("tool,@acme,npm,-,-,-,-", "@scope/name"),
+      ("tool,acme/tool,oci,-,-,-,-", "host/repository form"),
+      ("yq,mikefarah/yq,github,^yq_(OS,-,-,-", "not a valid regex"),
+      ("yq,mikefarah/yq,github,yq_[OS,-,-,-", "not a valid glob"),
+    ] {
+      let err = format!("{:#}", parse(&format!("{}\n", row)).unwrap_err());
+      assert!(err.starts_with("test.csv:2: "), "{}", err);
+      assert!(
+        err.contains(expected),
+        "expected '{}' in '{}'",
+        expected,
+        err
+      );
+    }
+  }
+
+  fn assets(names: &[&str]) -> Vec<DownloadInfo> {
+    names
+      .iter()
+      .map(|name| {
+        let url = Url::parse(&format!("https://example.com/download/{}", name)).unwrap();
+        DownloadInfo::from_url(url, mime::APPLICATION_OCTET_STREAM, 1)
+      })
+      .collect()
+  }
+
+  fn selected(
+    app: &SupportedApp,
+    names: &[&str],
+    version: &str,
+    os: TargetOs,
+    arch: TargetArch,
+  ) -> Vec<String> {
+    let target = TargetDeployment::new(os, arch);
+    let matched = app.match_assets(assets(names), version, &target).unwrap();
+    app
+      .pick_asset(matched, &target)
+      .into_iter()
+      .map(|asset| asset.name)
+      .collect()
+  }
+
+  #[test]
+  fn file_pattern_selects_one_asset() {
+    let apps = load_registry("apps.csv").unwrap();
+    let yq = &apps["yq"];
+    let yq_assets = [
+      "checksums",
+      "yq_darwin_arm64",
+      "yq_linux_amd64",
+      "yq_linux_amd64.tar.gz",
+      "yq_linux_arm64",
+      "yq_windows_amd64.exe",
+    ];
+    assert_eq!(
+      selected(yq, &yq_assets, "4.44.3", TargetOs::Linux, TargetArch::Amd64),
+      ["yq_linux_amd64"]
+    );
+    assert_eq!(
+      selected(
+        yq,
+        &yq_assets,
+        "4.44.3",
+        TargetOs::Windows,
+        TargetArch::Amd64
+      ),
+      ["yq_windows_amd64.exe"]
+    );
+
+    let gh_assets = [
+      "gh_2.62.0_linux_amd64.deb",
+      "gh_2.62.0_linux_amd64.tar.gz",
+      "gh_2.62.0_macOS_arm64.zip",
+    ];
+    assert_eq!(
+      selected(
+        &apps["gh"],
+        &gh_assets,
+        "2.62.0",
+        TargetOs::Mac,
+        TargetArch::Aarch64
+      ),
+      ["gh_2.62.0_macOS_arm64.zip"]
+    );
+    // versions are matched literally, not as regex wildcards
+    assert!(selected(
+      &apps["gh"],
+      &gh_assets,
+      "2.6.0",
+      TargetOs::Linux,
+      TargetArch::Amd64
+    )
+    .is_empty());
+
+    let shellcheck_assets = [
+      "shellcheck-v0.10.0.darwin.aarch64.tar.xz",
+      "shellcheck-v0.10.0.linux.x86_64.tar.xz",
+      "shellcheck-v0.10.0.zip",
+    ];
+    assert_eq!(
+      selected(
+        &apps["shellcheck"],
+        &shellcheck_assets,
+        "0.10.0",
+        TargetOs::Linux,
+        TargetArch::Amd64
+      ),
+      ["shellcheck-v0.10.0.linux.x86_64.tar.xz"]
+    );
+  }
+
+  #[test]
+  fn emulated_builds_back_up_missing_native_ones() {
+    let apps = load_registry("apps.csv").unwrap();
+    let yq = &apps["yq"];
+    let yq_assets = ["yq_darwin_amd64", "yq_windows_386.exe", "yq_linux_amd64"];
+    let matched = |os, arch| {
+      yq.match_assets(
+        assets(&yq_assets),
+        "4.44.3",
+        &TargetDeployment::new(os, arch),
+      )
+      .unwrap()
+      .into_iter()
+      .map(|asset| (asset.name, asset.fallback))
+      .collect::<Vec<_>>()
+    };
+    assert_eq!(
+      matched(TargetOs::Mac, TargetArch::Arm64),
+      [("yq_darwin_amd64".to_string(), Some(TargetArch::Amd64))]
+    );
+    assert_eq!(
+      matched(TargetOs::Windows, TargetArch::Amd64),
+      [("yq_windows_386.exe".to_string(), Some(TargetArch::x86))]
+    );
+    assert_eq!(
+      matched(TargetOs::Mac, TargetArch::Amd64),
+      [("yq_darwin_amd64".to_string(), None)]
+    );
+    assert!(matched(TargetOs::Linux, TargetArch::Arm64).is_empty());
+  }
+
+  #[test]
+  fn enterprise_repos_keep_their_api_root() {
+    let host = GithubHostConfig {
+      name: "ghe".to_string(),
+      api_url: "https://github.example.com/api/v3/".to_string(),
+      web_url: "https://github.example.com".to_string(),
+      token: None,
+      app: None,
+    };
+    let repo = Repo::github_enterprise(&host, "owner/repos");
+    assert_eq!(
+      repo.get_url().unwrap().as_str(),
+      "https://github.example.com/api/v3/repos/owner/repos"
+    );
+    assert_eq!(repo.get_github_repo().unwrap(), "owner/repos");
+    assert_eq!(
+      Repo::github("repos/tool").get_github_repo().unwrap(),
+      "repos/tool"
+    );
+  }
+
+  #[test]
+  fn unconfigured_hosts_do_not_fall_back_to_github_com() {
+    assert_eq!(Repo::github("owner/tool").github_host().unwrap(), None);
+
+    let host = GithubHostConfig {
+      name: "ghe".to_string(),
+      api_url: "https://github.example.com/api/v3".to_string(),
+      web_url: "https://github.example.com".to_string(),
+      token: None,
+      app: None,
+    };
+    let removed = Repo::github_enterprise(&host, "owner/tool");
+    assert!(matches!(
+      removed.github_host(),
+      Err(AppError::HostNotAllowed(name)) if name == "ghe"
+    ));
+    let unnamed = Repo::Github {
+      host: None,
+      url: "https://github.example.com/api/v3/repos/owner/tool".to_string(),
+    };
+    assert!(matches!(
+      unnamed.github_host(),
+      Err(AppError::InvalidInput(_))
+    ));
+  }
+
+  #[test]
+  fn custom_release_tags_round_trip() {
+    let apps = load_registry("apps.csv").unwrap();
+    let jq = &apps["jq"];
+    assert_eq!(jq.release_tag("1.7.1"), "jq-1.7.1");
+    assert_eq!(jq.release_tag("v1.7.1"), "jq-1.7.1");
+    assert_eq!(jq.release_tag("jq-1.7.1"), "jq-1.7.1");
+    assert_eq!(jq.release_tag("latest"), "latest");
+    assert_eq!(jq.version_from_tag("jq-1.7.1"), "1.7.1");
+
+    let yq = &apps["yq"];
+    assert_eq!(yq.release_tag("v4.44.3"), "v4.44.3");
+    assert_eq!(yq.version_from_tag("v4.44.3"), "4.44.3");
+    assert_eq!(yq.release_tags("4.44.3"), ["v4.44.3", "4.44.3"]);
+    assert_eq!(yq.release_tags("v4.44.3"), ["v4.44.3"]);
+    assert_eq!(jq.release_tags("1.7.1"), ["jq-1.7.1"]);
+  }
+
+  #[test]
+  fn libc_placeholder_follows_the_request() {
+    let apps = load_registry("apps.csv").unwrap();
+    let names = [
+      "uv-x86_64-unknown-linux-gnu.tar.gz",
+      "uv-x86_64-unknown-linux-musl.tar.gz",
+      "uv-x86_64-apple-darwin.tar.gz",
+    ];
+    let linux = TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64);
+    for (libc, expected) in [
+      (Some(TargetLibc::Musl), names[1]),
+      (Some(TargetLibc::Gnu), names[0]),
+      (Some(TargetLibc::None), names[1]),
+      (None, names[1]),
+    ] {
+      let target = linux.clone().with_libc(libc);
+      let matched = apps["uv"]
+        .match_assets(assets(&names), "0.5.4", &target)
+        .unwrap();
+      let matched: Vec<&str> = matched.iter().map(|asset| asset.name.as_str()).collect();
+      assert_eq!(matched, [expected], "{}", target);
+    }
+  }
+
+  #[test]
+  fn archive_binaries_expand_placeholders() {
+    let apps = load_registry("apps.csv").unwrap();
+    let mac_arm = TargetDeployment::new(TargetOs::Mac, TargetArch::Aarch64);
+    let binary = |path: &str, name: &str| ArchiveBinary {
+      archive_path: path.to_string(),
+      install_name: name.to_string(),
+      os: None,
+    };
+    assert_eq!(
+      apps["helm"].archive_binaries("3.16.2", &mac_arm),
+      [binary("darwin-arm64/helm", "helm")]
+    );
+    assert_eq!(
+      apps["shellcheck"].archive_binaries("0.10.0", &mac_arm),
+      [binary("shellcheck-v0.10.0/shellcheck", "shellcheck")]
+    );
+    assert_eq!(
+      apps["uv"].archive_binaries("0.5.4", &mac_arm),
+      [
+        binary("uv-aarch64-apple-darwin/uv", "uv"),
+        binary("uv-aarch64-apple-darwin/uvx", "uvx")
+      ]
+    );
+    assert!(apps["yq"].archive_binaries("4.44.3", &mac_arm).is_empty());
+
+    let linux = TargetDeployment::new(TargetOs::Linux, TargetArch::Amd64);
+    for (libc, triple) in [
+      (TargetLibc::Musl, "x86_64-unknown-linux-musl"),
+      (TargetLibc::Gnu, "x86_64-unknown-linux-gnu"),
+      (TargetLibc::None, "x86_64-unknown-linux-musl"),
+    ] {
+      assert_eq!(
+        apps["uv"].archive_binaries("0.5.4", &linux.clone().with_libc(libc)),
+        [
+          binary(&format!("uv-{}/uv", triple), "uv"),
+          binary(&format!("uv-{}/uvx", triple), "uvx")
+        ]
+      );
+    }
+
+    let windows = TargetDeployment::new(TargetOs::Windows, TargetArch::Amd64);
+    let windows_binary = |path: &str, name: &str| ArchiveBinary {
+      os: Some(TargetOs::Windows),
+      ..binary(path, name)
+    };
+    assert_eq!(
+      apps["uv"].archive_binaries("0.5.4", &windows),
+      [windows_binary("uv", "uv"), windows_binary("uvx", "uvx")]
+    );
+    assert_eq!(apps["uv"].archive_depth_for(&TargetOs::Windows), Some(0));
+    assert_eq!(apps["uv"].archive_depth_for(&TargetOs::Mac), Some(1));
+    assert_eq!(
+      apps["gh"].archive_binaries("2.62.0", &windows),
+      [windows_binary("bin/gh", "gh")]
+    );
+    assert_eq!(apps["gh"].archive_depth_for(&TargetOs::Windows), Some(1));
+
+    let renamed = parse("tool,example.com,url,tool.zip,dist/tool-cli=tool,1,-\n").unwrap();
+    assert_eq!(renamed["tool"].binaries, [binary("dist/tool-cli", "tool")]);
+  }
+
+  #[test]
+  fn apps_without_file_pattern_keep_every_asset() {
+    let app = SupportedApp::new("a/b", Repo::github("a/b"), AppSource::Github);
+    let names = ["tool_linux_amd64", "tool_linux_amd64.tar.gz"];
+    assert_eq!(
+      selected(&app, &names, "1.0.0", TargetOs::Linux, TargetArch::Amd64),
+      names
+    );
+  }
+
+  #[test]
+  fn rejects_duplicate_shortnames() {
+    let err = parse("yq,mikefarah/yq,github,-,-,-,-\nyq,other/yq,github,-,-,-,-\n").unwrap_err();
+    assert_eq!(err.to_string(), "test.csv:3: duplicate shortname 'yq'");
+  }
+}
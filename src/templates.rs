@@ -7,16 +7,44 @@ use tera::{Filter, Tera};
 
 pub(crate) static TEMPLATES: LazyLock<Tera> = LazyLock::new(|| {
   let mut tera = Tera::default();
-  let (install_sh, content) = ("install.sh", include_str!("../templates/install.sh"));
-  info!("adding template {}", install_sh);
-  tera
-    .add_raw_template(install_sh, content)
-    .unwrap_or_else(|e| panic!("failed to add {} template: {}", install_sh, e));
-  let (install_ps1, content) = ("install.ps1", include_str!("../templates/install.ps1"));
-  info!("adding template {}", install_ps1);
-  tera
-    .add_raw_template(install_ps1, content)
-    .unwrap_or_else(|e| panic!("failed to add {} template: {}", install_ps1, e));
+  for (name, content) in [
+    ("install.sh", include_str!("../templates/install.sh")),
+    ("install.ps1", include_str!("../templates/install.ps1")),
+    (
+      "install_python.sh",
+      include_str!("../templates/install_python.sh"),
+    ),
+    (
+      "install_python.ps1",
+      include_str!("../templates/install_python.ps1"),
+    ),
+    ("install_go.sh", include_str!("../templates/install_go.sh")),
+    (
+      "install_go.ps1",
+      include_str!("../templates/install_go.ps1"),
+    ),
+    (
+      "install_cargo.sh",
+      include_str!("../templates/install_cargo.sh"),
+    ),
+    (
+      "install_cargo.ps1",
+      include_str!("../templates/install_cargo.ps1"),
+    ),
+    (
+      "install_npm.sh",
+      include_str!("../templates/install_npm.sh"),
+    ),
+    (
+      "install_npm.ps1",
+      include_str!("../templates/install_npm.ps1"),
+    ),
+  ] {
+    info!("adding template {}", name);
+    tera
+      .add_raw_template(name, content)
+      .unwrap_or_else(|e| panic!("failed to add {} template: {}", name, e));
+  }
   tera.register_filter("escape_shell", ShellEscape);
   tera.register_filter("enumerate", Enumerate);
   tera
@@ -12,6 +12,12 @@ $RUN_DIRECTORY = $PWD.Path
 $_QUIET = {{ quiet | escape_shell }}
 $_FORCE = {{ force | escape_shell }}
 $_CANONICAL_BINARY_NAME = {{ app | escape_shell }}
+$_ARCHIVE_PATHS = @({% for binary in binaries %}'{{ binary.archive_path | replace(from="'", to="''") }}'{% if not loop.last %}, {% endif %}{% endfor %})
+$_INSTALL_NAMES = @({% for binary in binaries %}'{{ binary.install_name | replace(from="'", to="''") }}'{% if not loop.last %}, {% endif %}{% endfor %})
+$_ARCHIVE_DEPTH = {{ archive_depth }}
+$_PREFIX = '{{ prefix | replace(from="'", to="''") }}'
+# the default prefix is rendered literally, expand a leading $HOME or ~ here
+$_PREFIX = $_PREFIX -replace '^(\$HOME|~)', $HOME
 
 $_E_GENERIC_ERROR = 1
 
@@ -112,19 +118,44 @@ function Get-WebContent {
     }
 }
 
+# checks a downloaded file against the digest published for the chosen asset, if any
+function Test-Sha256 {
+    param(
+        [Parameter(Mandatory = $true)]
+        [string]$Path
+    )
+
+    $expected = $_sha256s[$choice]
+    if ([string]::IsNullOrEmpty($expected)) {
+        return
+    }
+    $actual = (Get-FileHash -Path $Path -Algorithm SHA256).Hash.ToLowerInvariant()
+    if ($actual -ne $expected) {
+        [Console]::Error.WriteLine("checksum mismatch for $(Split-Path $Path -Leaf): expected $expected, got $actual")
+        exit 100
+    }
+    Write-Host "Verified sha256 of $(Split-Path $Path -Leaf)"
+}
+
 #------------------------------------------------------------------------------
 # 05) Rendered Asset Arrays
 #------------------------------------------------------------------------------
 $_urls = @({% for asset in assets %}"{{ asset.url | escape_shell }}"{% if not loop.last %}, {% endif %}{% endfor %})
 $_filenames = @({% for asset in assets %}"{{ asset.name | escape_shell }}"{% if not loop.last %}, {% endif %}{% endfor %})
 $_filetypes = @({% for asset in assets %}"{{ asset.filetype | escape_shell }}"{% if not loop.last %}, {% endif %}{% endfor %})
-$_printables = @({% for asset in assets %}"{{ asset.name ~ " (" ~ asset.filetype ~ ")" | escape_shell }}"{% if not loop.last %}, {% endif %}{% endfor %})
+$_sha256s = @({% for asset in assets %}"{% if asset.sha256 %}{{ asset.sha256 | escape_shell }}{% endif %}"{% if not loop.last %}, {% endif %}{% endfor %})
+$_printables = @({% for asset in assets %}"{% if asset.fallback %}{{ asset.name ~ " (" ~ asset.filetype ~ ", " ~ asset.fallback ~ " under emulation)" | escape_shell }}{% else %}{{ asset.name ~ " (" ~ asset.filetype ~ ")" | escape_shell }}{% endif %}"{% if not loop.last %}, {% endif %}{% endfor %})
 
 #------------------------------------------------------------------------------
 # 06) Asset Selection
 #------------------------------------------------------------------------------
+{% if assets | length == 1 -%}
+Write-Host "Selected $($_printables[0])"
+$choice = 0
+{%- else -%}
 Write-Host "Please select one of the following:"
 $choice = Get-UserChoice -Choices $_printables -AllowQuit
+{%- endif %}
 
 #------------------------------------------------------------------------------
 # 07) Selection Validation
@@ -144,6 +175,40 @@ if ($choice -lt 0 -or $choice -ge $_urls.Count) {
 Write-Host "Downloading from $($_urls[$choice]) to $_TMPDIR"
 $_type = $_filetypes[$choice]
 
+{% if binaries | length > 0 -%}
+# the registry knows where the binaries live, extract only those
+$filename = $_filenames[$choice]
+$saved_file = Join-Path $_TMPDIR.FullName $filename
+if (-not (Get-WebContent -Url $_urls[$choice] -OutFile $saved_file)) {
+    [Console]::Error.WriteLine("failed downloading archive")
+    exit 100
+}
+Test-Sha256 -Path $saved_file
+$extract_dir = Join-Path $_TMPDIR.FullName "extracted"
+New-Item -ItemType Directory -Path $extract_dir -Force | Out-Null
+# bsdtar ships with Windows 10 1803+ and reads zip as well as tar archives
+tar -xf $saved_file -C $extract_dir --strip-components=$_ARCHIVE_DEPTH
+$bin_dir = Join-Path $_PREFIX "bin"
+if (-not (Test-Path $bin_dir)) {
+    New-Item -ItemType Directory -Path $bin_dir -Force | Out-Null
+}
+for ($idx = 0; $idx -lt $_ARCHIVE_PATHS.Count; $idx++) {
+    $leaf = Split-Path $_ARCHIVE_PATHS[$idx] -Leaf
+    $extracted_file = @((Join-Path $extract_dir $leaf), (Join-Path $extract_dir "$leaf.exe")) |
+        Where-Object { Test-Path $_ } | Select-Object -First 1
+    if (-not $extracted_file) {
+        [Console]::Error.WriteLine("$($_ARCHIVE_PATHS[$idx]) not found in $filename")
+        exit 100
+    }
+    $install_name = $_INSTALL_NAMES[$idx] + [IO.Path]::GetExtension($extracted_file)
+    $dest_path = Join-Path $bin_dir $install_name
+    Copy-Item $extracted_file $dest_path -Force
+    Write-Host "Installed $install_name to $dest_path"
+}
+& $cleanup
+exit 0
+{%- endif %}
+
 switch ($_type) {
     "binary" {
         $filename = $_filenames[$choice]
@@ -152,6 +217,7 @@ switch ($_type) {
             [Console]::Error.WriteLine("failed downloading binary asset")
             exit 100
         }
+        Test-Sha256 -Path $saved_file
 
         if ([string]::IsNullOrWhiteSpace($_CANONICAL_BINARY_NAME)) {
             $binary_name = Read-Host "enter alternate binary name (default: $filename)"
@@ -195,6 +261,7 @@ switch ($_type) {
             [Console]::Error.WriteLine("failed downloading msi installer")
             exit 100
         }
+        Test-Sha256 -Path $saved_file
         Write-Host "Launching MSI installer..."
         Start-Process msiexec.exe -ArgumentList "/i `"$saved_file`"" -Wait
     }
@@ -205,6 +272,7 @@ switch ($_type) {
             [Console]::Error.WriteLine("failed downloading exe installer")
             exit 100
         }
+        Test-Sha256 -Path $saved_file
         Write-Host "Launching EXE installer..."
         Start-Process -FilePath $saved_file -Wait
     }
@@ -217,6 +285,7 @@ switch ($_type) {
             [Console]::Error.WriteLine("failed downloading tar.gz archive")
             exit 100
         }
+        Test-Sha256 -Path $archive_path
 
         # Extract using tar (available in Windows 10 1803+) or 7-Zip if available
         if (Get-Command tar -ErrorAction SilentlyContinue) {
@@ -11,6 +11,15 @@ RUN_DIRECTORY="$PWD"
 _QUIET={{ quiet | escape_shell }}
 _FORCE={{ force | escape_shell }}
 _CANONICAL_BINARY_NAME={{ app | escape_shell }}
+_ARCHIVE_PATHS=( {% for binary in binaries %}{{ binary.archive_path | escape_shell }} {% endfor %})
+_INSTALL_NAMES=( {% for binary in binaries %}{{ binary.install_name | escape_shell }} {% endfor %})
+_ARCHIVE_DEPTH={{ archive_depth }}
+_LIBC={{ libc | escape_shell }}
+_AUTO_SELECT={{ auto_select }}
+_PREFIX={{ prefix | escape_shell }}
+# the default prefix is rendered literally, expand a leading $HOME or ~ here
+_PREFIX="${_PREFIX/#\$HOME/$HOME}"
+_PREFIX="${_PREFIX/#\~/$HOME}"
 
 _E_GENERIC_ERROR=1
 
@@ -100,6 +109,27 @@ _urlget() {
   fi
 }
 
+# checks a downloaded file against the digest published for the chosen asset, if any
+_verify_sha256() {
+  local expected="${_sha256s[$choice]}" actual
+  if [ -z "$expected" ]; then
+    return 0
+  fi
+  if command -v sha256sum &> /dev/null; then
+    actual="$(sha256sum "$1" | cut -d ' ' -f 1)"
+  elif command -v shasum &> /dev/null; then
+    actual="$(shasum -a 256 "$1" | cut -d ' ' -f 1)"
+  else
+    printf "neither sha256sum nor shasum found, skipping checksum verification\n" >&2
+    return 0
+  fi
+  if [ "$actual" != "$expected" ]; then
+    printf "checksum mismatch for %s: expected %s, got %s\n" "${1##*/}" "$expected" "$actual" >&2
+    exit 100
+  fi
+  printf "Verified sha256 of %s\n" "${1##*/}"
+}
+
 #------------------------------------------------------------------------------
 # 05) Rendered Asset Arrays
 #------------------------------------------------------------------------------
@@ -109,13 +139,70 @@ _urls=( {% for asset in assets %}
 )
 _filenames=( {% for asset in assets %}{{ asset.name | escape_shell }} {% endfor %})
 _filetypes=( {% for asset in assets %}{{ asset.filetype | escape_shell }} {% endfor %})
-_printables=( {% for asset in assets %}{{ asset.name ~ " (" ~ asset.filetype ~ ")" | escape_shell }} {% endfor %})
+_printables=( {% for asset in assets %}{% if asset.fallback %}{{ asset.name ~ " (" ~ asset.filetype ~ ", " ~ asset.fallback ~ " under emulation)" | escape_shell }}{% else %}{{ asset.name ~ " (" ~ asset.filetype ~ ")" | escape_shell }}{% endif %} {% endfor %})
+_libcs=( {% for asset in assets %}{{ asset.libc | escape_shell }} {% endfor %})
+_sha256s=( {% for asset in assets %}{% if asset.sha256 %}{{ asset.sha256 | escape_shell }}{% else %}''{% endif %} {% endfor %})
 
 #------------------------------------------------------------------------------
 # 06) Asset Selection
 #------------------------------------------------------------------------------
-printf "Please select one of the following:\n"
-choice="$(_ask_choices --quit "${_printables[@]}")"
+# the C library of the host: the requested libc, else musl or glibc on Linux and none elsewhere
+_host_libc() {
+  if [ -n "$_LIBC" ]; then
+    printf "%s\n" "$_LIBC"
+  elif [ "$(uname -s)" != "Linux" ]; then
+    printf "none\n"
+  elif ls /lib/ld-musl-* &> /dev/null || { ldd --version 2>&1 || true; } | grep -qi musl; then
+    printf "musl\n"
+  else
+    printf "gnu\n"
+  fi
+}
+_HOST_LIBC="$(_host_libc)"
+
+# glibc builds only run on glibc hosts, every other build runs anywhere
+_runs_here() {
+  [ "$1" != "gnu" ] || [ "$_HOST_LIBC" = "gnu" ]
+}
+
+{% if assets | length == 1 -%}
+if ! _runs_here "${_libcs[0]}"; then
+  printf "%s is a glibc build, which does not run on this %s host\n" "${_filenames[0]}" "$_HOST_LIBC" >&2
+  exit 100
+fi
+printf "Selected %s\n" "${_printables[0]}"
+choice=0
+{%- else -%}
+{% raw %}
+# leave out the builds the host cannot run
+_compatible=()
+for idx in "${!_urls[@]}"; do
+  if _runs_here "${_libcs[$idx]}"; then
+    _compatible+=("$idx")
+  fi
+done
+if [ "${#_compatible[@]}" -eq 0 ]; then
+  printf "only glibc builds were published, which do not run on this %s host\n" "$_HOST_LIBC" >&2
+  exit 100
+fi
+if [ "$_AUTO_SELECT" = true ] || [ "${#_compatible[@]}" -eq 1 ]; then
+  choice="${_compatible[0]}"
+  printf "Selected %s\n" "${_printables[$choice]}"
+else
+  printf "Please select one of the following:\n"
+  _compatible_printables=()
+  for idx in "${_compatible[@]}"; do
+    _compatible_printables+=("${_printables[$idx]}")
+  done
+  choice="$(_ask_choices --quit "${_compatible_printables[@]}")"
+  case "$choice" in
+    [0-9]*)
+      choice="${_compatible[$choice]:-invalid}"
+      ;;
+  esac
+fi
+{% endraw %}
+{%- endif %}
 
 #------------------------------------------------------------------------------
 # 07) Selection Validation
@@ -141,11 +228,53 @@ esac
 #------------------------------------------------------------------------------
 printf "Downloading from %s to %s\n" "${_urls[$choice]}" "$_TMPDIR"
 _type="${_filetypes[$choice]}"
+
+{% if binaries | length > 0 -%}
+# the registry knows where the binaries live, extract only those
+filename="${_filenames[$choice]}"
+saved_file="$_TMPDIR/$filename"
+_urlget "${_urls[$choice]}" > "$saved_file"
+_verify_sha256 "$saved_file"
+mkdir -p "$_TMPDIR/extracted"
+case "$filename" in
+  *.zip)
+    # Windows archives add .exe to the binaries' names, and unzip exits 11 for the unmatched form
+    _zip_paths=()
+    for archive_path in "${_ARCHIVE_PATHS[@]}"; do
+      _zip_paths+=("$archive_path" "$archive_path.exe")
+    done
+    unzip -q -o -j "$saved_file" "${_zip_paths[@]}" -d "$_TMPDIR/extracted" || [ "$?" -eq 11 ]
+    ;;
+  *)
+    tar -xf "$saved_file" -C "$_TMPDIR/extracted" --strip-components="$_ARCHIVE_DEPTH"
+    ;;
+esac
+mkdir -p "$_PREFIX/bin"
+for idx in "${!_ARCHIVE_PATHS[@]}"; do
+  archive_path="${_ARCHIVE_PATHS[$idx]}"
+  install_name="${_INSTALL_NAMES[$idx]}"
+  extracted_file="$_TMPDIR/extracted/${archive_path##*/}"
+  if [ ! -f "$extracted_file" ] && [ -f "$extracted_file.exe" ]; then
+    extracted_file="$extracted_file.exe"
+    install_name="$install_name.exe"
+  fi
+  if [ ! -f "$extracted_file" ]; then
+    printf "%s not found in %s\n" "$archive_path" "$filename" >&2
+    exit 100
+  fi
+  chmod +x "$extracted_file"
+  cp "$extracted_file" "$_PREFIX/bin/$install_name"
+  printf "Installed %s to %s\n" "$install_name" "$_PREFIX/bin/$install_name"
+done
+exit 0
+{%- endif %}
+
 case "$_type" in
   "binary" | "deb installer")
     filename="${_filenames[$choice]}"
     saved_file="$_TMPDIR/$filename"
     _urlget "${_urls[$choice]}" > "$saved_file"
+    _verify_sha256 "$saved_file"
 
     if [ "$_type" = "deb installer" ]; then
       if command -v dpkg &> /dev/null; then
@@ -175,7 +304,10 @@ case "$_type" in
     ;;
   "tar.gz")
     filename="${_filenames[$choice]}"
-    _urlget "${_urls[$choice]}" | tar xz
+    saved_file="$_TMPDIR/$filename"
+    _urlget "${_urls[$choice]}" > "$saved_file"
+    _verify_sha256 "$saved_file"
+    tar xzf "$saved_file"
     executable_files=(
       $(find . -type f -executable -exec printf '{} ' \;)
     )
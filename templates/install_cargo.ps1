@@ -0,0 +1,52 @@
+#requires -version 3.0
+
+#{# template engine Tera #}
+
+#------------------------------------------------------------------------------
+# 01) Runtime Setup
+#------------------------------------------------------------------------------
+Set-StrictMode -Version Latest
+$ErrorActionPreference = "Stop"
+$_CRATE = '{{ crate_name | replace(from="'", to="''") }}'
+$_VERSION = '{{ version | replace(from="'", to="''") }}'
+$_INDEX = '{{ index | replace(from="'", to="''") }}'
+$_BINSTALL_METADATA = ${{ binstall }}
+$_PREFIX = '{{ prefix | replace(from="'", to="''") }}'
+$_FORCE = ${{ force }}
+
+# the default prefix is rendered literally, expand a leading $HOME or ~ here
+$_PREFIX = $_PREFIX -replace '^(\$HOME|~)', $HOME
+$_REQUIREMENT = "$_CRATE@$_VERSION"
+
+$_extra_flags = @()
+if ($_INDEX) {
+    $_extra_flags += @("--index", $_INDEX)
+}
+if ($_FORCE) {
+    $_extra_flags += @("--force")
+}
+
+#------------------------------------------------------------------------------
+# 02) Prebuilt Binaries via cargo-binstall
+#------------------------------------------------------------------------------
+if (Get-Command cargo-binstall -ErrorAction SilentlyContinue) {
+    Write-Host "installing $_REQUIREMENT with cargo binstall into $(Join-Path $_PREFIX "bin")"
+    cargo binstall --no-confirm --root $_PREFIX @_extra_flags $_REQUIREMENT
+    exit $LASTEXITCODE
+}
+
+#------------------------------------------------------------------------------
+# 03) Build from Source
+#------------------------------------------------------------------------------
+if (-not (Get-Command cargo -ErrorAction SilentlyContinue)) {
+    [Console]::Error.WriteLine("$_CRATE has no prebuilt binary for this platform and is installed from crates,")
+    [Console]::Error.WriteLine("but neither cargo-binstall nor cargo was found on PATH. Install Rust from https://rustup.rs and run this script again.")
+    exit 100
+}
+if ($_BINSTALL_METADATA) {
+    [Console]::Error.WriteLine("$_CRATE publishes prebuilt binaries for cargo-binstall, install it to skip compiling")
+}
+
+Write-Host "building $_REQUIREMENT with cargo install into $(Join-Path $_PREFIX "bin")"
+cargo install --locked --root $_PREFIX @_extra_flags $_REQUIREMENT
+exit $LASTEXITCODE
@@ -0,0 +1,51 @@
+#!/usr/bin/env bash
+
+#{# template engine Tera #}
+
+#------------------------------------------------------------------------------
+# 01) Runtime Setup
+#------------------------------------------------------------------------------
+set -euo pipefail
+_CRATE={{ crate_name | escape_shell }}
+_VERSION={{ version | escape_shell }}
+_INDEX={{ index | escape_shell }}
+_BINSTALL_METADATA={{ binstall }}
+_PREFIX={{ prefix | escape_shell }}
+_FORCE={{ force }}
+
+# the default prefix is rendered literally, expand a leading $HOME or ~ here
+_PREFIX="${_PREFIX/#\$HOME/$HOME}"
+_PREFIX="${_PREFIX/#\~/$HOME}"
+_REQUIREMENT="$_CRATE@$_VERSION"
+
+_extra_flags=()
+if [ -n "$_INDEX" ]; then
+  _extra_flags+=(--index "$_INDEX")
+fi
+if [ "$_FORCE" = true ]; then
+  _extra_flags+=(--force)
+fi
+
+#------------------------------------------------------------------------------
+# 02) Prebuilt Binaries via cargo-binstall
+#------------------------------------------------------------------------------
+if command -v cargo-binstall &> /dev/null; then
+  printf "installing %s with cargo binstall into %s\n" "$_REQUIREMENT" "$_PREFIX/bin"
+  cargo binstall --no-confirm --root "$_PREFIX" ${_extra_flags[@]+"${_extra_flags[@]}"} "$_REQUIREMENT"
+  exit 0
+fi
+
+#------------------------------------------------------------------------------
+# 03) Build from Source
+#------------------------------------------------------------------------------
+if ! command -v cargo &> /dev/null; then
+  printf "%s has no prebuilt binary for this platform and is installed from crates,\n" "$_CRATE" >&2
+  printf "but neither cargo-binstall nor cargo was found on PATH. Install Rust from https://rustup.rs and run this script again.\n" >&2
+  exit 100
+fi
+if [ "$_BINSTALL_METADATA" = true ]; then
+  printf "%s publishes prebuilt binaries for cargo-binstall, install it to skip compiling\n" "$_CRATE" >&2
+fi
+
+printf "building %s with cargo install into %s\n" "$_REQUIREMENT" "$_PREFIX/bin"
+cargo install --locked --root "$_PREFIX" ${_extra_flags[@]+"${_extra_flags[@]}"} "$_REQUIREMENT"
@@ -0,0 +1,52 @@
+#requires -version 3.0
+
+#{# template engine Tera #}
+
+#------------------------------------------------------------------------------
+# 01) Runtime Setup
+#------------------------------------------------------------------------------
+Set-StrictMode -Version Latest
+$ErrorActionPreference = "Stop"
+$_PACKAGE = '{{ package | replace(from="'", to="''") }}'
+$_VERSION = '{{ go_version | replace(from="'", to="''") }}'
+$_BINARY_NAME = '{{ binary_name | replace(from="'", to="''") }}'
+$_PREFIX = '{{ prefix | replace(from="'", to="''") }}'
+$_FORCE = ${{ force }}
+
+# the default prefix is rendered literally, expand a leading $HOME or ~ here
+$_PREFIX = $_PREFIX -replace '^(\$HOME|~)', $HOME
+$_BIN_DIR = Join-Path $_PREFIX "bin"
+$_BINARY = Join-Path $_BIN_DIR "$_BINARY_NAME.exe"
+
+#------------------------------------------------------------------------------
+# 02) Go Toolchain Check
+#------------------------------------------------------------------------------
+if (-not (Get-Command go -ErrorAction SilentlyContinue)) {
+    [Console]::Error.WriteLine("$_PACKAGE has no prebuilt binary for this platform and is built from source with go install,")
+    [Console]::Error.WriteLine("but go was not found on PATH. Install Go from https://go.dev/dl/ and run this script again.")
+    exit 100
+}
+
+if ((Test-Path $_BINARY) -and -not $_FORCE) {
+    [Console]::Error.WriteLine("$_BINARY already exists, pass force=true to replace it")
+    exit 100
+}
+
+#------------------------------------------------------------------------------
+# 03) Build and Install
+#------------------------------------------------------------------------------
+if (-not (Test-Path $_BIN_DIR)) {
+    New-Item -ItemType Directory -Path $_BIN_DIR -Force | Out-Null
+}
+Write-Host "building $_PACKAGE@$_VERSION with $(go version) into $_BIN_DIR"
+$env:GOBIN = $_BIN_DIR
+go install "$_PACKAGE@$_VERSION"
+if ($LASTEXITCODE -ne 0) {
+    exit $LASTEXITCODE
+}
+
+if (-not (Test-Path $_BINARY)) {
+    [Console]::Error.WriteLine("go install did not produce $_BINARY")
+    exit 100
+}
+Write-Host "Installed $_BINARY_NAME to $_BINARY"
@@ -0,0 +1,45 @@
+#!/usr/bin/env bash
+
+#{# template engine Tera #}
+
+#------------------------------------------------------------------------------
+# 01) Runtime Setup
+#------------------------------------------------------------------------------
+set -euo pipefail
+_PACKAGE={{ package | escape_shell }}
+_VERSION={{ go_version | escape_shell }}
+_BINARY_NAME={{ binary_name | escape_shell }}
+_PREFIX={{ prefix | escape_shell }}
+_FORCE={{ force }}
+
+# the default prefix is rendered literally, expand a leading $HOME or ~ here
+_PREFIX="${_PREFIX/#\$HOME/$HOME}"
+_PREFIX="${_PREFIX/#\~/$HOME}"
+_BIN_DIR="$_PREFIX/bin"
+
+#------------------------------------------------------------------------------
+# 02) Go Toolchain Check
+#------------------------------------------------------------------------------
+if ! command -v go &> /dev/null; then
+  printf "%s has no prebuilt binary for this platform and is built from source with go install,\n" "$_PACKAGE" >&2
+  printf "but go was not found on PATH. Install Go from https://go.dev/dl/ and run this script again.\n" >&2
+  exit 100
+fi
+
+if [ -e "$_BIN_DIR/$_BINARY_NAME" ] && [ "$_FORCE" != true ]; then
+  printf "%s already exists, pass force=true to replace it\n" "$_BIN_DIR/$_BINARY_NAME" >&2
+  exit 100
+fi
+
+#------------------------------------------------------------------------------
+# 03) Build and Install
+#------------------------------------------------------------------------------
+mkdir -p "$_BIN_DIR"
+printf "building %s@%s with %s into %s\n" "$_PACKAGE" "$_VERSION" "$(go version)" "$_BIN_DIR"
+GOBIN="$_BIN_DIR" go install "$_PACKAGE@$_VERSION"
+
+if [ ! -x "$_BIN_DIR/$_BINARY_NAME" ]; then
+  printf "go install did not produce %s\n" "$_BIN_DIR/$_BINARY_NAME" >&2
+  exit 100
+fi
+printf "Installed %s to %s\n" "$_BINARY_NAME" "$_BIN_DIR/$_BINARY_NAME"
@@ -0,0 +1,56 @@
+#requires -version 3.0
+
+#{# template engine Tera #}
+
+#------------------------------------------------------------------------------
+# 01) Runtime Setup
+#------------------------------------------------------------------------------
+Set-StrictMode -Version Latest
+$ErrorActionPreference = "Stop"
+$_PACKAGE = '{{ package | replace(from="'", to="''") }}'
+$_VERSION = '{{ version | replace(from="'", to="''") }}'
+$_TARBALL = '{{ tarball | replace(from="'", to="''") }}'
+$_REGISTRY = '{{ registry_url | replace(from="'", to="''") }}'
+$_BINARIES = @({% for binary in binaries %}'{{ binary | replace(from="'", to="''") }}'{% if not loop.last %}, {% endif %}{% endfor %})
+$_PREFIX = '{{ prefix | replace(from="'", to="''") }}'
+$_FORCE = ${{ force }}
+
+# the default prefix is rendered literally, expand a leading $HOME or ~ here
+$_PREFIX = $_PREFIX -replace '^(\$HOME|~)', $HOME
+# npm puts global shims straight into its prefix on Windows, so bin is used as the npm prefix
+$_BIN_DIR = Join-Path $_PREFIX "bin"
+
+#------------------------------------------------------------------------------
+# 02) Node.js Check
+#------------------------------------------------------------------------------
+if (-not (Get-Command npm -ErrorAction SilentlyContinue)) {
+    [Console]::Error.WriteLine("$_PACKAGE is distributed on npm, but npm was not found on PATH.")
+    [Console]::Error.WriteLine("Install Node.js from https://nodejs.org and run this script again.")
+    exit 100
+}
+
+foreach ($binary in $_BINARIES) {
+    $shim = Join-Path $_BIN_DIR "$binary.cmd"
+    if ((Test-Path $shim) -and -not $_FORCE) {
+        [Console]::Error.WriteLine("$shim already exists, pass force=true to replace it")
+        exit 100
+    }
+}
+
+#------------------------------------------------------------------------------
+# 03) Install
+#------------------------------------------------------------------------------
+# the resolved tarball pins the exact version, dependencies still come from the registry
+$_extra_flags = @()
+if ($_FORCE) {
+    $_extra_flags += @("--force")
+}
+Write-Host "installing $_PACKAGE@$_VERSION with npm into $_BIN_DIR"
+npm install --global --prefix $_BIN_DIR --registry $_REGISTRY @_extra_flags $_TARBALL
+if ($LASTEXITCODE -ne 0) {
+    exit $LASTEXITCODE
+}
+
+foreach ($binary in $_BINARIES) {
+    Write-Host "Installed $binary to $(Join-Path $_BIN_DIR "$binary.cmd")"
+}
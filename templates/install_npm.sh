@@ -0,0 +1,51 @@
+#!/usr/bin/env bash
+
+#{# template engine Tera #}
+
+#------------------------------------------------------------------------------
+# 01) Runtime Setup
+#------------------------------------------------------------------------------
+set -euo pipefail
+_PACKAGE={{ package | escape_shell }}
+_VERSION={{ version | escape_shell }}
+_TARBALL={{ tarball | escape_shell }}
+_REGISTRY={{ registry_url | escape_shell }}
+_BINARIES=({% for binary in binaries %}{{ binary | escape_shell }} {% endfor %})
+_PREFIX={{ prefix | escape_shell }}
+_FORCE={{ force }}
+
+# the default prefix is rendered literally, expand a leading $HOME or ~ here
+_PREFIX="${_PREFIX/#\$HOME/$HOME}"
+_PREFIX="${_PREFIX/#\~/$HOME}"
+_BIN_DIR="$_PREFIX/bin"
+
+#------------------------------------------------------------------------------
+# 02) Node.js Check
+#------------------------------------------------------------------------------
+if ! command -v npm &> /dev/null; then
+  printf "%s is distributed on npm, but npm was not found on PATH.\n" "$_PACKAGE" >&2
+  printf "Install Node.js from https://nodejs.org and run this script again.\n" >&2
+  exit 100
+fi
+
+for binary in ${_BINARIES[@]+"${_BINARIES[@]}"}; do
+  if [ -e "$_BIN_DIR/$binary" ] && [ "$_FORCE" != true ]; then
+    printf "%s already exists, pass force=true to replace it\n" "$_BIN_DIR/$binary" >&2
+    exit 100
+  fi
+done
+
+#------------------------------------------------------------------------------
+# 03) Install
+#------------------------------------------------------------------------------
+# the resolved tarball pins the exact version, dependencies still come from the registry
+_force_flag=()
+if [ "$_FORCE" = true ]; then
+  _force_flag=(--force)
+fi
+printf "installing %s@%s with npm into %s\n" "$_PACKAGE" "$_VERSION" "$_PREFIX"
+npm install --global --prefix "$_PREFIX" --registry "$_REGISTRY" ${_force_flag[@]+"${_force_flag[@]}"} "$_TARBALL"
+
+for binary in ${_BINARIES[@]+"${_BINARIES[@]}"}; do
+  printf "Installed %s to %s\n" "$binary" "$_BIN_DIR/$binary"
+done
@@ -0,0 +1,72 @@
+#requires -version 3.0
+
+#{# template engine Tera #}
+
+#------------------------------------------------------------------------------
+# 01) Runtime Setup
+#------------------------------------------------------------------------------
+Set-StrictMode -Version Latest
+$ErrorActionPreference = "Stop"
+$_PACKAGE = '{{ package | replace(from="'", to="''") }}'
+$_VERSION = '{{ version | replace(from="'", to="''") }}'
+$_INDEX_URL = '{{ index_url | replace(from="'", to="''") }}'
+$_BINARY_NAME = '{{ app | replace(from="'", to="''") }}'
+$_PREFIX = '{{ prefix | replace(from="'", to="''") }}'
+$_FORCE = ${{ force }}
+
+# the default prefix is rendered literally, expand a leading $HOME or ~ here
+$_PREFIX = $_PREFIX -replace '^(\$HOME|~)', $HOME
+$_REQUIREMENT = "$_PACKAGE==$_VERSION"
+$_BIN_DIR = Join-Path $_PREFIX "bin"
+if (-not (Test-Path $_BIN_DIR)) {
+    New-Item -ItemType Directory -Path $_BIN_DIR -Force | Out-Null
+}
+
+#------------------------------------------------------------------------------
+# 02) Isolated Tool Installers
+#------------------------------------------------------------------------------
+$_force_flag = @()
+if ($_FORCE) {
+    $_force_flag = @("--force")
+}
+
+if (Get-Command uv -ErrorAction SilentlyContinue) {
+    Write-Host "installing $_REQUIREMENT with uv tool install into $_BIN_DIR"
+    $env:UV_TOOL_DIR = Join-Path $_PREFIX "share\uv\tools"
+    $env:UV_TOOL_BIN_DIR = $_BIN_DIR
+    uv tool install @_force_flag --index-url $_INDEX_URL $_REQUIREMENT
+    exit $LASTEXITCODE
+}
+
+if (Get-Command pipx -ErrorAction SilentlyContinue) {
+    Write-Host "installing $_REQUIREMENT with pipx into $_BIN_DIR"
+    $env:PIPX_HOME = Join-Path $_PREFIX "share\pipx"
+    $env:PIPX_BIN_DIR = $_BIN_DIR
+    pipx install @_force_flag --index-url $_INDEX_URL $_REQUIREMENT
+    exit $LASTEXITCODE
+}
+
+#------------------------------------------------------------------------------
+# 03) Virtual Environment Fallback
+#------------------------------------------------------------------------------
+$_python = Get-Command python3, python, py -ErrorAction SilentlyContinue | Select-Object -First 1
+if (-not $_python) {
+    [Console]::Error.WriteLine("none of uv, pipx or python found, unable to install $_PACKAGE")
+    exit 100
+}
+
+$_VENV = Join-Path $_PREFIX "share\termlibs\venvs\$_PACKAGE"
+if ((Test-Path $_VENV) -and $_FORCE) {
+    Remove-Item $_VENV -Recurse -Force
+}
+Write-Host "installing $_REQUIREMENT into a virtual environment at $_VENV"
+& $_python.Source -m venv $_VENV
+& (Join-Path $_VENV "Scripts\python.exe") -m pip install --quiet --index-url $_INDEX_URL $_REQUIREMENT
+
+$_entrypoint = Join-Path $_VENV "Scripts\$_BINARY_NAME.exe"
+if (-not (Test-Path $_entrypoint)) {
+    [Console]::Error.WriteLine("$_PACKAGE did not provide a $_BINARY_NAME executable")
+    exit 100
+}
+Copy-Item $_entrypoint (Join-Path $_BIN_DIR "$_BINARY_NAME.exe") -Force
+Write-Host "Installed $_BINARY_NAME to $(Join-Path $_BIN_DIR "$_BINARY_NAME.exe")"
@@ -0,0 +1,73 @@
+#!/usr/bin/env bash
+
+#{# template engine Tera #}
+
+#------------------------------------------------------------------------------
+# 01) Runtime Setup
+#------------------------------------------------------------------------------
+set -euo pipefail
+_PACKAGE={{ package | escape_shell }}
+_VERSION={{ version | escape_shell }}
+_INDEX_URL={{ index_url | escape_shell }}
+_BINARY_NAME={{ app | escape_shell }}
+_PREFIX={{ prefix | escape_shell }}
+_FORCE={{ force }}
+
+# the default prefix is rendered literally, expand a leading $HOME or ~ here
+_PREFIX="${_PREFIX/#\$HOME/$HOME}"
+_PREFIX="${_PREFIX/#\~/$HOME}"
+_REQUIREMENT="$_PACKAGE==$_VERSION"
+_BIN_DIR="$_PREFIX/bin"
+mkdir -p "$_BIN_DIR"
+
+#------------------------------------------------------------------------------
+# 02) Isolated Tool Installers
+#------------------------------------------------------------------------------
+_force_flag=()
+if [ "$_FORCE" = true ]; then
+  _force_flag=(--force)
+fi
+
+if command -v uv &> /dev/null; then
+  printf "installing %s with uv tool install into %s\n" "$_REQUIREMENT" "$_BIN_DIR"
+  UV_TOOL_DIR="$_PREFIX/share/uv/tools" UV_TOOL_BIN_DIR="$_BIN_DIR" \
+    uv tool install ${_force_flag[@]+"${_force_flag[@]}"} --index-url "$_INDEX_URL" "$_REQUIREMENT"
+  exit 0
+fi
+
+if command -v pipx &> /dev/null; then
+  printf "installing %s with pipx into %s\n" "$_REQUIREMENT" "$_BIN_DIR"
+  PIPX_HOME="$_PREFIX/share/pipx" PIPX_BIN_DIR="$_BIN_DIR" \
+    pipx install ${_force_flag[@]+"${_force_flag[@]}"} --index-url "$_INDEX_URL" "$_REQUIREMENT"
+  exit 0
+fi
+
+#------------------------------------------------------------------------------
+# 03) Virtual Environment Fallback
+#------------------------------------------------------------------------------
+_python=""
+for candidate in python3 python; do
+  if command -v "$candidate" &> /dev/null; then
+    _python="$candidate"
+    break
+  fi
+done
+if [ -z "$_python" ]; then
+  printf "none of uv, pipx or python3 found, unable to install %s\n" "$_PACKAGE" >&2
+  exit 100
+fi
+
+_VENV="$_PREFIX/share/termlibs/venvs/$_PACKAGE"
+if [ -d "$_VENV" ] && [ "$_FORCE" = true ]; then
+  rm -rf "$_VENV"
+fi
+printf "installing %s into a virtual environment at %s\n" "$_REQUIREMENT" "$_VENV"
+"$_python" -m venv "$_VENV"
+"$_VENV/bin/python" -m pip install --quiet --index-url "$_INDEX_URL" "$_REQUIREMENT"
+
+if [ ! -x "$_VENV/bin/$_BINARY_NAME" ]; then
+  printf "%s did not provide a %s executable\n" "$_PACKAGE" "$_BINARY_NAME" >&2
+  exit 100
+fi
+ln -sf "$_VENV/bin/$_BINARY_NAME" "$_BIN_DIR/$_BINARY_NAME"
+printf "Installed %s to %s\n" "$_BINARY_NAME" "$_BIN_DIR/$_BINARY_NAME"